@@ -0,0 +1,222 @@
+//! This module implements the applies API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/applies).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let logs = client.applies().logs("apply-47MBvjwzBG8YKc2v").await.unwrap();
+//!     println!("{}", String::from_utf8_lossy(&logs));
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, Relationship},
+        logs::{self, Source, TailOptions},
+    },
+    bytes::Bytes,
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents an apply resource.
+pub type Apply = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single apply.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents possible states of an apply.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Pending,
+    ManagedQueued,
+    Queued,
+    Running,
+    Errored,
+    Canceled,
+    Finished,
+    Unreachable,
+    /// a status that is not known to this library
+    #[serde(other)]
+    Unknown,
+}
+impl Status {
+    /// returns true if the apply can not make any further progress
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Status::Errored
+                | Status::Canceled
+                | Status::Finished
+                | Status::Unreachable
+        )
+    }
+}
+/// This struct represents the time an apply entered each status.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct StatusTimestamps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canceled_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errored_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managed_queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+}
+/// This struct represents attributes of an apply.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_details: Option<crate::plans::ExecutionDetails>,
+    /// pre-signed URL of the raw apply logs, which is valid for one minute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_read_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_additions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_changes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_destructions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_imports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_timestamps: Option<StatusTimestamps>,
+}
+/// This struct represents relationships of an apply.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_versions: Option<Relationship<Many>>,
+}
+// ────────────────────────────────────────────────────────────
+/// Applies implements the applies API.
+#[derive(Debug, Clone)]
+pub struct Applies<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns applies API
+    pub fn applies(&self) -> Applies<'_> {
+        Applies { client: self }
+    }
+}
+impl<'a> Applies<'a> {
+    /// reads an apply
+    pub async fn read(&self, apply_id: &str) -> Result<Response, Error> {
+        validate_id("apply ID", apply_id)?;
+        self.client
+            .get(format!("applies/{apply_id}"), None, None)
+            .await
+    }
+    /// downloads raw logs of an apply
+    pub async fn logs(&self, apply_id: &str) -> Result<Bytes, Error> {
+        let url = self.log_read_url(apply_id).await?;
+        self.client.download(url.as_str(), None).await
+    }
+    /// tails logs of a apply and returns their chunks as they become
+    /// available. The stream ends once the end of the log is reached, and
+    /// after the first error.
+    ///
+    /// ## Parameters
+    /// * `apply_id` : ID of the apply
+    /// * `options` : chunk size, polling intervals and deadline
+    pub fn tail_logs(
+        &self,
+        apply_id: &str,
+        options: &TailOptions,
+    ) -> impl Stream<Item = Result<Bytes, Error>> + 'a {
+        logs::stream(self.client, Source::Apply(apply_id.to_string()), options)
+    }
+    /// returns pre-signed URL of the raw apply logs
+    pub async fn log_read_url(&self, apply_id: &str) -> Result<String, Error> {
+        let apply = self.read(apply_id).await?.into_data();
+        apply
+            .attributes
+            .and_then(|attributes| attributes.log_read_url)
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| Error::Deserialize {
+                path: "data.attributes.log-read-url".to_string(),
+                reason: format!(
+                    "apply `{apply_id}` does not have a log read URL"
+                ),
+            })
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "applies::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "applies::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/applies
+        let input = json!({
+            "data": {
+                "id": "apply-47MBvjwzBG8YKc2v",
+                "type": "applies",
+                "attributes": {
+                    "execution-details": { "mode": "remote" },
+                    "status": "running",
+                    "status-timestamps": {
+                        "queued-at": "2018-10-17T18:58:27+00:00",
+                        "started-at": "2018-10-17T18:58:29+00:00"
+                    },
+                    "log-read-url": "https://archivist.terraform.io/v1/object/dmF1bHQ6djE6bWJDcnBGcVhzbmJjMzFhYVhBYkhCdGJRdkNkeW1lemM",
+                    "resource-additions": 1,
+                    "resource-changes": 0,
+                    "resource-destructions": 0,
+                    "resource-imports": 0
+                },
+                "relationships": {
+                    "state-versions": {
+                        "data": [{ "id": "sv-TpnsuD3iewwsfeRD", "type": "state-versions" }]
+                    }
+                }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.status, Status::Running);
+        assert!(!attributes.status.is_final());
+        assert_eq!(attributes.resource_additions, Some(1));
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,254 @@
+//! This module resolves API tokens the same way Terraform CLI does.
+//!
+//! For a given hostname, sources are checked in the following order:
+//!
+//! 1. `TF_TOKEN_<host>` environment variable, where periods in the hostname
+//!    are encoded as underscores and hyphens as double underscores, e.g
+//!    `TF_TOKEN_app_terraform_io` or `TF_TOKEN_tfe__internal_example_com`.
+//! 2. `credentials "<host>" { token = "..." }` blocks of the CLI
+//!    configuration file, which is read from `TF_CLI_CONFIG_FILE`, falling
+//!    back to `~/.terraformrc` (`%APPDATA%/terraform.rc` on Windows).
+//! 3. `credentials.tfrc.json` file that `terraform login` writes to
+//!    `~/.terraform.d` (`%APPDATA%/terraform.d` on Windows).
+use {
+    regex::Regex,
+    std::{
+        collections::HashMap,
+        path::{Path, PathBuf},
+    },
+    zeroize::Zeroizing,
+};
+/// prefix of host-specific environment variables
+const ENV_TOKEN_PREFIX: &str = "TF_TOKEN_";
+/// environment variables that point to the CLI configuration file
+const ENV_CLI_CONFIG_FILE: &str = "TF_CLI_CONFIG_FILE";
+const ENV_CLI_CONFIG_FILE_LEGACY: &str = "TERRAFORM_CONFIG";
+/// name of the file `terraform login` stores tokens in
+const CREDENTIALS_FILE_NAME: &str = "credentials.tfrc.json";
+// ────────────────────────────────────────────────────────────
+/// returns the API token for the given hostname, or `None` if none of the
+/// sources has a token for it.
+pub fn lookup(hostname: &str) -> Option<Zeroizing<String>> {
+    let span: tracing::Span =
+        tracing::span!(tracing::Level::INFO, "credentials");
+    let _guard = span.enter();
+    if let Some(token) = from_env_vars(hostname, std::env::vars()) {
+        tracing::debug!("\nusing token from `{}`", env_var_name(hostname));
+        return Some(token);
+    }
+    if let Some(path) = cli_config_file() {
+        if let Some(token) = read_file(path.as_path())
+            .and_then(|content| from_cli_config(hostname, content.as_str()))
+        {
+            tracing::debug!("\nusing token from {:?}", path);
+            return Some(token);
+        }
+    }
+    if let Some(path) = credentials_file() {
+        if let Some(token) = read_file(path.as_path()).and_then(|content| {
+            from_credentials_json(hostname, content.as_str())
+        }) {
+            tracing::debug!("\nusing token from {:?}", path);
+            return Some(token);
+        }
+    }
+    None
+}
+/// returns the name of the environment variable that holds the token of the
+/// given hostname, e.g `TF_TOKEN_app_terraform_io`.
+pub fn env_var_name(hostname: &str) -> String {
+    let encoded = hostname.trim().replace('-', "__").replace('.', "_");
+    [ENV_TOKEN_PREFIX, encoded.as_str()].concat()
+}
+/// returns path of the CLI configuration file
+pub fn cli_config_file() -> Option<PathBuf> {
+    for name in [ENV_CLI_CONFIG_FILE, ENV_CLI_CONFIG_FILE_LEGACY] {
+        if let Some(path) = std::env::var_os(name).filter(|v| !v.is_empty()) {
+            return Some(PathBuf::from(path));
+        }
+    }
+    if cfg!(windows) {
+        return std::env::var_os("APPDATA")
+            .map(|dir| PathBuf::from(dir).join("terraform.rc"));
+    }
+    home_dir().map(|dir| dir.join(".terraformrc"))
+}
+/// returns path of the `credentials.tfrc.json` file
+pub fn credentials_file() -> Option<PathBuf> {
+    if cfg!(windows) {
+        return std::env::var_os("APPDATA").map(|dir| {
+            PathBuf::from(dir)
+                .join("terraform.d")
+                .join(CREDENTIALS_FILE_NAME)
+        });
+    }
+    home_dir().map(|dir| dir.join(".terraform.d").join(CREDENTIALS_FILE_NAME))
+}
+// ────────────────────────────────────────────────────────────
+/// returns current user's home directory
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+/// reads a file, returning `None` when it does not exist or is unreadable
+fn read_file(path: &Path) -> Option<String> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => Some(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            tracing::warn!("\nfailed to read {:?}: {}", path, e);
+            None
+        }
+    }
+}
+/// compares two hostnames the way Terraform CLI does
+fn same_host(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+/// decodes `TF_TOKEN_*` environment variables and returns the token of the
+/// given hostname
+fn from_env_vars<I>(hostname: &str, vars: I) -> Option<Zeroizing<String>>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    vars.into_iter()
+        .filter(|(_, value)| !value.trim().is_empty())
+        .find(|(name, _)| {
+            name.strip_prefix(ENV_TOKEN_PREFIX)
+                .map(|encoded| encoded.replace("__", "-").replace('_', "."))
+                .map_or(false, |decoded| same_host(decoded.as_str(), hostname))
+        })
+        .map(|(_, value)| Zeroizing::new(value.trim().to_string()))
+}
+/// JSON representation of credentials in CLI configuration files
+#[derive(Debug, Default, serde::Deserialize)]
+struct CredentialsConfig {
+    #[serde(default)]
+    credentials: HashMap<String, HostCredentials>,
+}
+/// credentials of a single host
+#[derive(serde::Deserialize)]
+struct HostCredentials {
+    #[serde(default)]
+    token: Option<String>,
+}
+impl std::fmt::Debug for HostCredentials {
+    /// SECURITY: the token is never printed
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HostCredentials")
+            .field("token", &self.token.as_ref().map(|_| "[REDACTED]"))
+            .finish()
+    }
+}
+/// returns the token of the given hostname from a `credentials.tfrc.json`
+/// document
+fn from_credentials_json(
+    hostname: &str,
+    content: &str,
+) -> Option<Zeroizing<String>> {
+    let config: CredentialsConfig = serde_json::from_str(content)
+        .map_err(|e| tracing::warn!("\nmalformed credentials file: {}", e))
+        .ok()?;
+    config
+        .credentials
+        .into_iter()
+        .find(|(host, _)| same_host(host, hostname))
+        .and_then(|(_, creds)| creds.token)
+        .filter(|token| !token.is_empty())
+        .map(Zeroizing::new)
+}
+/// returns the token of the given hostname from a CLI configuration file,
+/// which is either in HCL or JSON syntax
+fn from_cli_config(
+    hostname: &str,
+    content: &str,
+) -> Option<Zeroizing<String>> {
+    if content.trim_start().starts_with('{') {
+        return from_credentials_json(hostname, content);
+    }
+    // NOTE: only `credentials` blocks are of interest, so a full HCL parser
+    // is not needed.
+    let comments = Regex::new(r"(?s:/\*.*?\*/)|(?m:(#|//).*$)").unwrap();
+    let content = comments.replace_all(content, "");
+    let blocks =
+        Regex::new(r#"credentials\s+"([^"]+)"\s*\{([^}]*)\}"#).unwrap();
+    let attribute = Regex::new(r#"token\s*=\s*"([^"]*)""#).unwrap();
+    let token = blocks
+        .captures_iter(content.as_ref())
+        .filter(|block| same_host(&block[1], hostname))
+        .find_map(|block| {
+            attribute
+                .captures(&block[2])
+                .map(|captures| captures[1].to_string())
+        });
+    token.filter(|token| !token.is_empty()).map(Zeroizing::new)
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "client::credentials::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "client::credentials::tests" --nocapture'
+    use super::*;
+    #[test]
+    fn test_env_var_name() {
+        assert_eq!(
+            env_var_name("app.terraform.io"),
+            "TF_TOKEN_app_terraform_io"
+        );
+        assert_eq!(
+            env_var_name("tfe-internal.example.com"),
+            "TF_TOKEN_tfe__internal_example_com"
+        );
+    }
+    #[test]
+    fn test_from_env_vars() {
+        let vars = vec![
+            ("TF_TOKEN_app_terraform_io".to_string(), "a".to_string()),
+            (
+                "TF_TOKEN_tfe__internal_example_com".to_string(),
+                "b".to_string(),
+            ),
+        ];
+        let actual = from_env_vars("tfe-internal.example.com", vars.clone());
+        assert_eq!(actual.as_deref().map(String::as_str), Some("b"));
+        let actual = from_env_vars("App.Terraform.io", vars.clone());
+        assert_eq!(actual.as_deref().map(String::as_str), Some("a"));
+        assert!(from_env_vars("example.com", vars).is_none());
+    }
+    #[test]
+    fn test_from_credentials_json() {
+        let content = r#"{
+            "credentials": {
+                "app.terraform.io": { "token": "json-token" }
+            }
+        }"#;
+        let actual = from_credentials_json("app.terraform.io", content);
+        assert_eq!(actual.as_deref().map(String::as_str), Some("json-token"));
+        assert!(from_credentials_json("example.com", content).is_none());
+        assert!(from_credentials_json("example.com", "not json").is_none());
+    }
+    #[test]
+    fn test_from_cli_config() {
+        let content = r#"
+            plugin_cache_dir = "$HOME/.terraform.d/plugin-cache"
+            # credentials "app.terraform.io" { token = "commented-out" }
+            credentials "app.terraform.io" {
+              token = "hcl-token"
+            }
+            credentials "tfe.example.com" {
+              token = "other-token"
+            }
+        "#;
+        let actual = from_cli_config("app.terraform.io", content);
+        assert_eq!(actual.as_deref().map(String::as_str), Some("hcl-token"));
+        let actual = from_cli_config("tfe.example.com", content);
+        assert_eq!(actual.as_deref().map(String::as_str), Some("other-token"));
+        assert!(from_cli_config("example.com", content).is_none());
+        let content = r#"{"credentials": {"app.terraform.io": {"token": "json-token"}}}"#;
+        let actual = from_cli_config("app.terraform.io", content);
+        assert_eq!(actual.as_deref().map(String::as_str), Some("json-token"));
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,672 @@
+//! This module implements the HCP Terraform/Enterprise API client.
+//!
+//! The client wraps `crate::core::ReqwestClient`, resolves relative endpoint
+//! paths against the configured address and API base path and injects the
+//! `Authorization: Bearer` header into every request.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! use tfe_oxide::core::HttpClient;
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::client::Builder::new()
+//!         .set_token("my-api-token")
+//!         .unwrap()
+//!         .build()
+//!         .unwrap();
+//!     // NOTE: sends a request to https://app.terraform.io/api/v2/account/details
+//!     let response: Result<serde_json::Value, tfe_oxide::core::Error> =
+//!         client.get("account/details", None, None).await;
+//!     println!("{:?}", response);
+//! }
+//! ```
+/// This module resolves API tokens from Terraform CLI configuration files and
+/// environment variables.
+pub mod credentials;
+// ────────────────────────────────────────────────────────────
+use {
+    crate::core::{Headers, HttpClient, Query},
+    bytes::Bytes,
+    maybe_async::async_impl,
+    reqwest::Url,
+    serde::{de::DeserializeOwned, Serialize},
+    std::fmt::Debug,
+    tokio::io::AsyncSeekExt,
+    zeroize::Zeroizing,
+};
+/// Default values
+const DEFAULT_ADDRESS: &str = "https://app.terraform.io";
+const DEFAULT_BASE_PATH: &str = "/api/v2/";
+/// Environment variables that are used when a value was not explicitly set
+const ENV_ADDRESS: &str = "TFE_ADDRESS";
+const ENV_HOSTNAME: &str = "TFE_HOSTNAME";
+const ENV_TOKEN: &str = "TFE_TOKEN";
+// ────────────────────────────────────────────────────────────
+/// Client is an authenticated HCP Terraform/Enterprise API client.
+///
+/// Endpoint paths passed to `HttpClient` methods are relative to the API base
+/// path (e.g `workspaces/ws-123`), while fully-qualified URLs are sent as-is.
+///
+/// SECURITY: the API token is only sent to the configured address.
+/// Authenticated requests to fully-qualified URLs of other hosts are rejected
+/// with `Error::InvalidInput`; use `Client::download` and `Client::upload`
+/// for pre-signed URLs instead.
+#[derive(Clone)]
+pub struct Client {
+    /// underlying HTTP client
+    http: crate::core::ReqwestClient,
+    /// settings the underlying HTTP client was built with
+    http_settings: crate::core::ReqwestClientBuilder,
+    /// scheme and host of the API server, e.g `https://app.terraform.io`
+    address: String,
+    /// path prefix of every API endpoint, e.g `/api/v2/`
+    base_path: String,
+    /// API token sent as a bearer token
+    token: Zeroizing<String>,
+}
+// ────────────────────────────────────────────────────────────
+impl Debug for Client {
+    /// SECURITY: the API token is never printed
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Client")
+            .field("http", &self.http)
+            .field("http_settings", &self.http_settings)
+            .field("address", &self.address)
+            .field("base_path", &self.base_path)
+            .field("token", &"[REDACTED]")
+            .finish()
+    }
+}
+// ────────────────────────────────────────────────────────────
+impl Client {
+    /// creates a new api client for the given token, using default values
+    /// and environment variables for everything else.
+    pub fn new(
+        token: &str,
+    ) -> miette::Result<Self, Box<dyn std::error::Error>> {
+        Builder::new().set_token(token)?.build()
+    }
+    /// returns scheme and host of the API server
+    pub fn get_address(&self) -> &str {
+        self.address.as_str()
+    }
+    /// returns API base path
+    pub fn get_base_path(&self) -> &str {
+        self.base_path.as_str()
+    }
+    /// returns hostname of the API server
+    pub fn get_hostname(&self) -> String {
+        Url::parse(self.address.as_str())
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_string))
+            .unwrap_or_default()
+    }
+    /// returns the underlying HTTP client, which does not send the API token
+    pub fn get_http(&self) -> &crate::core::ReqwestClient {
+        &self.http
+    }
+    /// returns timeout and retry settings the underlying HTTP client was
+    /// built with
+    pub fn get_http_settings(&self) -> &crate::core::ReqwestClientBuilder {
+        &self.http_settings
+    }
+    /// returns the request quota of the API token reported by the last
+    /// response, if any
+    pub fn get_rate_limit(&self) -> Option<crate::core::RateLimit> {
+        self.http.get_rate_limit()
+    }
+    /// returns fully-qualified URL of an endpoint.
+    ///
+    /// ## Parameters
+    /// * `path` : endpoint path relative to API base path. Absolute `http://`
+    ///   and `https://` URLs are returned unchanged.
+    pub fn url(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            return path.to_string();
+        }
+        [
+            self.address.as_str(),
+            self.base_path.as_str(),
+            path.trim_start_matches('/'),
+        ]
+        .concat()
+    }
+    /// returns true if the URL has the same scheme, host and port as the
+    /// configured address, i.e the API token may be sent to it
+    pub fn is_api_url(&self, url: &str) -> bool {
+        match (Url::parse(url), Url::parse(self.address.as_str())) {
+            (Ok(url), Ok(address)) => url.origin() == address.origin(),
+            _ => false,
+        }
+    }
+    /// returns fully-qualified URL of an endpoint the API token is sent to.
+    /// Fully-qualified URLs of other hosts than the configured address are
+    /// rejected.
+    fn endpoint(&self, path: &str) -> Result<String, crate::core::Error> {
+        let url = self.url(path);
+        if !self.is_api_url(url.as_str()) {
+            return Err(crate::core::Error::InvalidInput {
+                reason: format!(
+                    "refusing to send the API token to `{url}`, which is not on `{}`",
+                    self.address
+                ),
+            });
+        }
+        Ok(url)
+    }
+    /// downloads a resource that is not JSON, e.g logs, without sending the
+    /// API token.
+    ///
+    /// It is meant for pre-signed URLs the API returns, e.g `log-read-url`
+    /// of plans, which point to a different host that must not receive the
+    /// token.
+    ///
+    /// ## Parameters
+    /// * `url` : fully-qualified URL of the resource
+    /// * `query` : optional query parameters
+    pub async fn download(
+        &self,
+        url: &str,
+        query: Option<&Query<'_>>,
+    ) -> Result<Bytes, crate::core::Error> {
+        self.http.get_bytes(self.url(url), None, query).await
+    }
+    /// sends an authenticated GET request to an endpoint that does not
+    /// return JSON, e.g configuration version downloads, and returns server
+    /// response body as raw bytes.
+    ///
+    /// ## Parameters
+    /// * `path` : endpoint path relative to API base path
+    /// * `query` : optional query parameters
+    pub async fn get_raw(
+        &self,
+        path: &str,
+        query: Option<&Query<'_>>,
+    ) -> Result<Bytes, crate::core::Error> {
+        let url = self.endpoint(path)?;
+        let headers = self.headers(None);
+        self.http.get_bytes(url, Some(&headers), query).await
+    }
+    /// uploads a raw body, e.g an archive, with a PUT request without
+    /// sending the API token.
+    ///
+    /// It is meant for pre-signed URLs the API returns, e.g `upload-url` of
+    /// configuration versions.
+    ///
+    /// ## Parameters
+    /// * `url` : fully-qualified URL the body is uploaded to
+    /// * `content_type` : content type of the body
+    /// * `body` : request body
+    pub async fn upload(
+        &self,
+        url: &str,
+        content_type: &str,
+        body: Bytes,
+    ) -> Result<(), crate::core::Error> {
+        let mut headers = Headers::new();
+        headers.insert(
+            reqwest::header::CONTENT_TYPE.to_string(),
+            content_type.to_string(),
+        );
+        self.http
+            .put_bytes(self.url(url), Some(&headers), body)
+            .await
+    }
+    /// uploads a file with a PUT request without sending the API token. The
+    /// file is streamed rather than read into memory, thus the request is
+    /// not retried when it fails.
+    ///
+    /// It is meant for pre-signed URLs the API returns, e.g `upload-url` of
+    /// configuration versions.
+    ///
+    /// ## Parameters
+    /// * `url` : fully-qualified URL the file is uploaded to
+    /// * `content_type` : content type of the file
+    /// * `file` : file to upload, which is read from its current position
+    pub async fn upload_file(
+        &self,
+        url: &str,
+        content_type: &str,
+        file: tokio::fs::File,
+    ) -> Result<(), crate::core::Error> {
+        let read_error = |e: std::io::Error| crate::core::Error::Transport {
+            reason: format!("failed to read the file to upload: {e}"),
+        };
+        let mut file = file;
+        let length = file.metadata().await.map_err(read_error)?.len();
+        let offset = file.stream_position().await.map_err(read_error)?;
+        let mut headers = Headers::new();
+        headers.insert(
+            reqwest::header::CONTENT_TYPE.to_string(),
+            content_type.to_string(),
+        );
+        self.http
+            .put_stream(
+                self.url(url),
+                Some(&headers),
+                reqwest::Body::from(file),
+                length.saturating_sub(offset),
+            )
+            .await
+    }
+    /// returns a copy of the given headers that includes the authorization
+    /// header.
+    fn headers(&self, headers: Option<&Headers>) -> Headers {
+        let mut res: Headers = headers.cloned().unwrap_or_default();
+        res.insert(
+            reqwest::header::AUTHORIZATION.to_string(),
+            format!("Bearer {}", self.token.as_str()),
+        );
+        res
+    }
+}
+// ────────────────────────────────────────────────────────────
+#[async_impl]
+impl HttpClient for Client {
+    #[inline]
+    async fn get<R, S>(
+        &self,
+        url: S,
+        headers: Option<&Headers>,
+        payload: Option<&Query>,
+    ) -> Result<R, crate::core::Error>
+    where
+        R: DeserializeOwned + Serialize + Debug,
+        S: AsRef<str> + Sync + Send,
+    {
+        let url = self.endpoint(url.as_ref())?;
+        let headers = self.headers(headers);
+        self.http.get(url, Some(&headers), payload).await
+    }
+
+    #[inline]
+    async fn post<R, S, T>(
+        &self,
+        url: S,
+        headers: Option<&Headers>,
+        payload: T,
+    ) -> Result<R, crate::core::Error>
+    where
+        R: DeserializeOwned + Serialize + Debug,
+        S: AsRef<str> + Sync + Send,
+        T: Serialize + Debug + Send + Sync,
+    {
+        let url = self.endpoint(url.as_ref())?;
+        let headers = self.headers(headers);
+        self.http.post(url, Some(&headers), payload).await
+    }
+
+    #[inline]
+    async fn put<R, S, T>(
+        &self,
+        url: S,
+        headers: Option<&Headers>,
+        payload: T,
+    ) -> Result<R, crate::core::Error>
+    where
+        R: DeserializeOwned + Serialize + Debug,
+        S: AsRef<str> + Sync + Send,
+        T: Serialize + Debug + Send + Sync,
+    {
+        let url = self.endpoint(url.as_ref())?;
+        let headers = self.headers(headers);
+        self.http.put(url, Some(&headers), payload).await
+    }
+
+    #[inline]
+    async fn patch<R, S, T>(
+        &self,
+        url: S,
+        headers: Option<&Headers>,
+        payload: T,
+    ) -> Result<R, crate::core::Error>
+    where
+        R: DeserializeOwned + Serialize + Debug,
+        S: AsRef<str> + Sync + Send,
+        T: Serialize + Debug + Send + Sync,
+    {
+        let url = self.endpoint(url.as_ref())?;
+        let headers = self.headers(headers);
+        self.http.patch(url, Some(&headers), payload).await
+    }
+
+    #[inline]
+    async fn delete<R, S, T>(
+        &self,
+        url: S,
+        headers: Option<&Headers>,
+        payload: T,
+    ) -> Result<R, crate::core::Error>
+    where
+        R: DeserializeOwned + Serialize + Debug,
+        S: AsRef<str> + Sync + Send,
+        T: Serialize + Debug + Send + Sync,
+    {
+        let url = self.endpoint(url.as_ref())?;
+        let headers = self.headers(headers);
+        self.http.delete(url, Some(&headers), payload).await
+    }
+}
+// ────────────────────────────────────────────────────────────
+/// Builder is used to build an authenticated API client.
+///
+/// Values that are not explicitly set are read from the following environment
+/// variables:
+///
+/// * `TFE_ADDRESS` : scheme and host of the API server
+/// * `TFE_HOSTNAME` : host of the API server, used when `TFE_ADDRESS` is unset
+/// * `TFE_TOKEN` : API token
+///
+/// When neither an API token nor `TFE_TOKEN` is set, the token is resolved
+/// from Terraform CLI credentials of the API server hostname. See
+/// `credentials` module for details.
+#[derive(Clone, Default)]
+pub struct Builder {
+    /// scheme and host of the API server
+    address: Option<String>,
+    /// path prefix of every API endpoint
+    base_path: Option<String>,
+    /// API token
+    token: Option<Zeroizing<String>>,
+    /// builder of the underlying HTTP client
+    http: crate::core::ReqwestClientBuilder,
+}
+// ────────────────────────────────────────────────────────────
+impl Debug for Builder {
+    /// SECURITY: the API token is never printed
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Builder")
+            .field("address", &self.address)
+            .field("base_path", &self.base_path)
+            .field("token", &self.token.as_ref().map(|_| "[REDACTED]"))
+            .field("http", &self.http)
+            .finish()
+    }
+}
+// ────────────────────────────────────────────────────────────
+impl Builder {
+    /// creates a new API client builder
+    pub fn new() -> Self {
+        Builder::default()
+    }
+    /// sets scheme and host of the API server. `https` is assumed when the
+    /// scheme is missing.
+    pub fn set_address(
+        self,
+        arg: &str,
+    ) -> miette::Result<Self, Box<dyn std::error::Error>> {
+        let mut res = self.clone();
+        res.address = Some(normalize_address(arg)?);
+        Ok(res)
+    }
+    /// return scheme and host of the API server
+    pub fn get_address(&self) -> Option<&str> {
+        self.address.as_deref()
+    }
+    /// sets API base path
+    pub fn set_base_path(
+        self,
+        arg: &str,
+    ) -> miette::Result<Self, Box<dyn std::error::Error>> {
+        let mut res = self.clone();
+        res.base_path = Some(normalize_base_path(arg));
+        Ok(res)
+    }
+    /// return API base path
+    pub fn get_base_path(&self) -> Option<&str> {
+        self.base_path.as_deref()
+    }
+    /// sets API token
+    pub fn set_token(
+        self,
+        arg: &str,
+    ) -> miette::Result<Self, Box<dyn std::error::Error>> {
+        let mut res = self.clone();
+        res.token = Some(Zeroizing::new(arg.to_string()));
+        Ok(res)
+    }
+    /// returns true if an API token was explicitly set
+    pub fn has_token(&self) -> bool {
+        self.token.is_some()
+    }
+    /// sets builder of the underlying HTTP client, which controls timeout and
+    /// retry settings
+    pub fn set_http(
+        self,
+        arg: crate::core::ReqwestClientBuilder,
+    ) -> miette::Result<Self, Box<dyn std::error::Error>> {
+        let mut res = self.clone();
+        res.http = arg;
+        Ok(res)
+    }
+    /// return builder of the underlying HTTP client
+    pub fn get_http(&self) -> &crate::core::ReqwestClientBuilder {
+        &self.http
+    }
+    /// builds and returns an authenticated API client
+    pub fn build(self) -> miette::Result<Client, Box<dyn std::error::Error>> {
+        let address = match self.address {
+            Some(address) => address,
+            None => resolve_address(
+                std::env::var(ENV_ADDRESS).ok(),
+                std::env::var(ENV_HOSTNAME).ok(),
+            )?,
+        };
+        let base_path = self
+            .base_path
+            .unwrap_or_else(|| DEFAULT_BASE_PATH.to_string());
+        let hostname = Url::parse(address.as_str())?
+            .host_str()
+            .unwrap_or_default()
+            .to_string();
+        let token = match self.token {
+            Some(token) => token,
+            None => std::env::var(ENV_TOKEN)
+                .ok()
+                .filter(|v| !v.trim().is_empty())
+                .map(Zeroizing::new)
+                .or_else(|| credentials::lookup(hostname.as_str()))
+                .ok_or_else(|| crate::core::Error::InvalidInput {
+                    reason: format!(
+                        "missing API token for `{hostname}`. set it explicitly, through `{ENV_TOKEN}` or `{}` environment variables or run `terraform login {hostname}`",
+                        credentials::env_var_name(hostname.as_str())
+                    ),
+                })?,
+        };
+        Ok(Client {
+            http: self.http.clone().build()?,
+            http_settings: self.http,
+            address,
+            base_path,
+            token,
+        })
+    }
+}
+// ────────────────────────────────────────────────────────────
+/// returns an error if `value` is not a valid resource ID or name, which must
+/// only contain alphanumeric characters, dashes, underscores and periods.
+///
+/// ## Parameters
+/// * `name` : name of the argument, used in the error message
+/// * `value` : value of the argument
+pub(crate) fn validate_id(
+    name: &str,
+    value: &str,
+) -> Result<(), crate::core::Error> {
+    let valid = !value.is_empty()
+        && value.chars().all(|c| {
+            c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')
+        });
+    if !valid {
+        return Err(crate::core::Error::InvalidInput {
+            reason: format!("invalid value for {name}: `{value}`"),
+        });
+    }
+    Ok(())
+}
+/// returns the API server address from environment variable values, falling
+/// back to HCP Terraform.
+fn resolve_address(
+    address: Option<String>,
+    hostname: Option<String>,
+) -> miette::Result<String, Box<dyn std::error::Error>> {
+    let address = address
+        .filter(|v| !v.trim().is_empty())
+        .or_else(|| hostname.filter(|v| !v.trim().is_empty()));
+    match address {
+        Some(address) => normalize_address(address.as_str()),
+        None => Ok(DEFAULT_ADDRESS.to_string()),
+    }
+}
+/// ensures address has a scheme and host and does not end with a slash
+fn normalize_address(
+    arg: &str,
+) -> miette::Result<String, Box<dyn std::error::Error>> {
+    let arg = arg.trim();
+    let arg = if arg.contains("://") {
+        arg.to_string()
+    } else {
+        format!("https://{arg}")
+    };
+    let url = Url::parse(arg.as_str())?;
+    if url.host_str().is_none() {
+        return Err(Box::new(crate::core::Error::InvalidInput {
+            reason: format!("invalid API address `{arg}`"),
+        }));
+    }
+    Ok(arg.trim_end_matches('/').to_string())
+}
+/// ensures base path starts and ends with a slash
+fn normalize_base_path(arg: &str) -> String {
+    let arg = arg.trim().trim_matches('/');
+    if arg.is_empty() {
+        return "/".to_string();
+    }
+    format!("/{arg}/")
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "client::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "client::tests" --nocapture'
+    use super::*;
+    #[test]
+    fn set_address() {
+        let builder = Builder::new().set_address("tfe.example.com/").unwrap();
+        assert_eq!(builder.get_address(), Some("https://tfe.example.com"));
+        let builder =
+            Builder::new().set_address("http://localhost:8080").unwrap();
+        assert_eq!(builder.get_address(), Some("http://localhost:8080"));
+    }
+    #[test]
+    fn set_base_path() {
+        let builder = Builder::new().set_base_path("api/v2").unwrap();
+        assert_eq!(builder.get_base_path(), Some("/api/v2/"));
+        let builder = Builder::new().set_base_path("/").unwrap();
+        assert_eq!(builder.get_base_path(), Some("/"));
+    }
+    #[test]
+    fn test_validate_id() {
+        assert!(validate_id("workspace ID", "ws-4j8p6jX1w33MiDC7").is_ok());
+        assert!(validate_id("organization", "my_org.io").is_ok());
+        assert!(validate_id("workspace ID", "").is_err());
+        assert!(validate_id("workspace name", "../ws").is_err());
+        assert!(validate_id("workspace name", "a b").is_err());
+    }
+    #[test]
+    fn resolve_address_precedence() {
+        let actual = resolve_address(None, None).unwrap();
+        assert_eq!(actual, DEFAULT_ADDRESS);
+        let actual =
+            resolve_address(None, Some("tfe.example.com".to_string()))
+                .unwrap();
+        assert_eq!(actual, "https://tfe.example.com");
+        let actual = resolve_address(
+            Some("https://tfe.example.org".to_string()),
+            Some("tfe.example.com".to_string()),
+        )
+        .unwrap();
+        assert_eq!(actual, "https://tfe.example.org");
+    }
+    #[tokio::test]
+    async fn build() {
+        let client = Builder::new()
+            .set_address("tfe.example.com")
+            .unwrap()
+            .set_token("secret-token")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(client.get_hostname(), "tfe.example.com");
+        assert_eq!(
+            client.url("workspaces/ws-123"),
+            "https://tfe.example.com/api/v2/workspaces/ws-123"
+        );
+        assert_eq!(
+            client.url("/workspaces/ws-123"),
+            "https://tfe.example.com/api/v2/workspaces/ws-123"
+        );
+        assert_eq!(
+            client.url("https://archivist.terraform.io/v1/object/abc"),
+            "https://archivist.terraform.io/v1/object/abc"
+        );
+        let headers = client.headers(None);
+        assert_eq!(
+            headers.get("authorization").map(String::as_str),
+            Some("Bearer secret-token")
+        );
+        assert!(!format!("{client:?}").contains("secret-token"));
+    }
+    #[tokio::test]
+    async fn test_token_is_only_sent_to_address() {
+        let client = Builder::new()
+            .set_address("tfe.example.com")
+            .unwrap()
+            .set_token("secret-token")
+            .unwrap()
+            .build()
+            .unwrap();
+        assert!(client.is_api_url("https://tfe.example.com/api/v2/runs"));
+        assert!(!client.is_api_url("http://tfe.example.com/api/v2/runs"));
+        assert!(!client.is_api_url("https://tfe.example.com:8443/api"));
+        assert!(!client.is_api_url("https://tfe.example.com.evil.io/api"));
+        let actual: Result<serde_json::Value, _> = client
+            .get("https://attacker.example.org/steal", None, None)
+            .await;
+        assert!(actual.unwrap_err().is_invalid_input());
+        let actual: Result<serde_json::Value, _> = client
+            .post("https://attacker.example.org/steal", None, ())
+            .await;
+        assert!(actual.unwrap_err().is_invalid_input());
+        let actual = client
+            .get_raw("https://attacker.example.org/steal", None)
+            .await;
+        assert!(actual.unwrap_err().is_invalid_input());
+    }
+    #[tokio::test]
+    async fn test_token_is_sent_to_absolute_api_url() {
+        let server =
+            crate::testing::Server::start(vec![crate::testing::Reply::json(
+                serde_json::json!({}),
+            )])
+            .await;
+        let client = server.client();
+        let url =
+            format!("{}/api/v2/runs?page%5Bnumber%5D=2", server.address());
+        let actual: Result<serde_json::Value, _> =
+            client.get(url, None, None).await;
+        assert!(actual.is_ok());
+        let received = server.received();
+        assert_eq!(received[0].target, "/api/v2/runs?page%5Bnumber%5D=2");
+        assert_eq!(
+            received[0].headers.get("authorization").map(String::as_str),
+            Some("Bearer test-token")
+        );
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,217 @@
+//! This module packs a directory into a gzip-compressed tarball that can be
+//! uploaded as a configuration version.
+//!
+//! Paths that `.terraformignore` rules exclude are left out. Symbolic links
+//! are archived as links rather than followed, and links whose target is
+//! outside of the directory are rejected with an
+//! `std::io::ErrorKind::InvalidInput` error.
+use {
+    super::ignore::Rules,
+    flate2::{write::GzEncoder, Compression},
+    std::{
+        fs::File,
+        io::{Seek, SeekFrom, Write},
+        path::{Component, Path, PathBuf},
+    },
+};
+/// content type of packed archives
+pub const CONTENT_TYPE: &str = "application/octet-stream";
+// ────────────────────────────────────────────────────────────
+/// packs a directory into a gzip-compressed tarball and returns it.
+///
+/// ## Parameters
+/// * `root` : directory to pack. Its `.terraformignore` file, if any, decides
+///   the paths that are excluded.
+pub fn pack(root: &Path) -> std::io::Result<Vec<u8>> {
+    pack_into(root, Vec::new())
+}
+/// packs a directory into a gzip-compressed tarball that is written to an
+/// anonymous temporary file, which is deleted once closed. The returned file
+/// is rewound, so that it can be read, e.g uploaded, without keeping the
+/// archive in memory.
+///
+/// ## Parameters
+/// * `root` : directory to pack. Its `.terraformignore` file, if any, decides
+///   the paths that are excluded.
+pub fn pack_file(root: &Path) -> std::io::Result<File> {
+    let mut file = pack_into(root, tempfile::tempfile()?)?;
+    file.seek(SeekFrom::Start(0))?;
+    Ok(file)
+}
+/// packs a directory into a gzip-compressed tarball that is written to
+/// `writer`, and returns the writer.
+///
+/// ## Parameters
+/// * `root` : directory to pack. Its `.terraformignore` file, if any, decides
+///   the paths that are excluded.
+/// * `writer` : destination of the archive
+pub fn pack_into<W: Write>(root: &Path, writer: W) -> std::io::Result<W> {
+    let span: tracing::Span = tracing::span!(tracing::Level::INFO, "pack");
+    let _guard = span.enter();
+    let rules = Rules::read(root)?;
+    let mut builder =
+        tar::Builder::new(GzEncoder::new(writer, Compression::default()));
+    builder.follow_symlinks(false);
+    append_dir(&mut builder, root, "", &rules)?;
+    let mut encoder = builder.into_inner()?;
+    encoder.flush()?;
+    encoder.finish()
+}
+/// appends contents of a directory to the archive, recursively.
+///
+/// ## Parameters
+/// * `dir` : directory whose contents are appended
+/// * `prefix` : path of the directory relative to the root, which is empty
+///   for the root itself
+fn append_dir<W: Write>(
+    builder: &mut tar::Builder<W>,
+    dir: &Path,
+    prefix: &str,
+    rules: &Rules,
+) -> std::io::Result<()> {
+    let mut entries =
+        std::fs::read_dir(dir)?.collect::<Result<Vec<_>, _>>()?;
+    // NOTE: sorting keeps archives of the same directory identical
+    entries.sort_by_key(std::fs::DirEntry::file_name);
+    for entry in entries {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = [prefix, name.as_str()].concat();
+        let file_type = entry.file_type()?;
+        let is_dir = file_type.is_dir();
+        if is_dir {
+            if !rules.is_excluded(path.as_str(), true) {
+                builder.append_dir(path.as_str(), entry.path())?;
+            }
+            // NOTE: descends into excluded directories too as negated rules
+            // might include paths below them.
+            let prefix = [path.as_str(), "/"].concat();
+            append_dir(builder, entry.path().as_path(), &prefix, rules)?;
+        } else if !rules.is_excluded(path.as_str(), false) {
+            if file_type.is_symlink() {
+                check_link(entry.path().as_path(), path.as_str())?;
+            }
+            tracing::trace!("\nadding `{}`", path);
+            builder.append_path_with_name(entry.path(), path.as_str())?;
+        }
+    }
+    Ok(())
+}
+/// returns an error if the target of a symbolic link is outside of the
+/// packed directory, since it would be resolved against the file system of
+/// the machine the archive is unpacked on.
+///
+/// ## Parameters
+/// * `link` : path of the symbolic link
+/// * `path` : path of the symbolic link relative to the root
+fn check_link(link: &Path, path: &str) -> std::io::Result<()> {
+    let target = std::fs::read_link(link)?;
+    // NOTE: the target is resolved lexically, relative to the directory of
+    // the link, whose path relative to the root is known
+    let mut resolved = PathBuf::new();
+    let mut escapes = target.is_absolute();
+    let parent = Path::new(path).parent().unwrap_or_else(|| Path::new(""));
+    for component in parent.components().chain(target.components()) {
+        match component {
+            Component::Normal(v) => resolved.push(v),
+            Component::ParentDir => escapes |= !resolved.pop(),
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => escapes = true,
+        }
+    }
+    if escapes {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "symbolic link `{path}` points to `{}`, which is outside of \
+                 the directory",
+                target.display()
+            ),
+        ));
+    }
+    Ok(())
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "configuration_versions::archive::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "configuration_versions::archive::tests" --nocapture'
+    use {super::*, flate2::read::GzDecoder};
+    #[test]
+    fn test_pack() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let root = dir.path();
+        for dir in [".git", ".terraform/modules/vpc", ".terraform/providers"] {
+            std::fs::create_dir_all(root.join(dir))?;
+        }
+        for file in [
+            "main.tf",
+            ".git/HEAD",
+            ".terraform/modules/vpc/main.tf",
+            ".terraform/providers/provider",
+        ] {
+            std::fs::write(root.join(file), "")?;
+        }
+        let archive = pack(root)?;
+        let mut actual = paths(archive.as_slice())?;
+        actual.sort();
+        // NOTE: archives written to a file are identical
+        let file = pack_file(root)?;
+        let mut expected = paths(file)?;
+        expected.sort();
+        assert_eq!(actual, expected);
+        assert_eq!(
+            actual,
+            vec![
+                ".terraform/modules",
+                ".terraform/modules/vpc",
+                ".terraform/modules/vpc/main.tf",
+                "main.tf",
+            ]
+        );
+        Ok(())
+    }
+    #[cfg(unix)]
+    #[test]
+    fn test_pack_symlinks() -> std::io::Result<()> {
+        use std::os::unix::fs::symlink;
+        let dir = tempfile::tempdir()?;
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("modules/vpc"))?;
+        std::fs::write(root.join("main.tf"), "")?;
+        symlink("../../main.tf", root.join("modules/vpc/main.tf"))?;
+        symlink("modules/vpc", root.join("vpc"))?;
+        let mut actual = paths(pack(root)?.as_slice())?;
+        actual.sort();
+        assert_eq!(
+            actual,
+            vec![
+                "main.tf",
+                "modules",
+                "modules/vpc",
+                "modules/vpc/main.tf",
+                "vpc"
+            ]
+        );
+        for target in ["../../../main.tf", "/etc/passwd", "./../vpc/../../.."]
+        {
+            std::fs::remove_file(root.join("modules/vpc/main.tf"))?;
+            symlink(target, root.join("modules/vpc/main.tf"))?;
+            let actual = pack(root).unwrap_err();
+            assert_eq!(actual.kind(), std::io::ErrorKind::InvalidInput);
+        }
+        Ok(())
+    }
+    /// returns paths of the entries of a gzip-compressed tarball
+    fn paths<R: std::io::Read>(archive: R) -> std::io::Result<Vec<String>> {
+        tar::Archive::new(GzDecoder::new(archive))
+            .entries()?
+            .map(|entry| {
+                entry.and_then(|entry| {
+                    Ok(entry.path()?.to_string_lossy().to_string())
+                })
+            })
+            .collect()
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,192 @@
+//! This module implements `.terraformignore` rules, which decide the files
+//! of a directory that are left out of a configuration version.
+//!
+//! Rules follow the semantics of Terraform CLI:
+//!
+//! - empty lines and lines starting with `#` are skipped
+//! - `!` negates a rule, i.e it includes paths an earlier rule excluded
+//! - `*` matches anything but `/`, `?` matches a single character but `/` and
+//!   `**` matches any number of directories
+//! - a trailing `/` only matches directories
+//! - a leading `/` anchors the rule to the root directory, otherwise the rule
+//!   matches at any depth
+//! - a rule that matches a directory also matches everything below it
+//! - the last matching rule wins
+//!
+//! When a directory has no `.terraformignore` file, `.git/` and
+//! `.terraform/` are excluded except for `.terraform/modules/`.
+use {
+    regex::Regex,
+    std::path::{Path, PathBuf},
+};
+/// name of the file rules are read from
+pub const IGNORE_FILE_NAME: &str = ".terraformignore";
+/// rules that are used when a directory has no ignore file
+const DEFAULT_RULES: &str = ".git/\n.terraform/\n!.terraform/modules/\n";
+// ────────────────────────────────────────────────────────────
+/// This struct represents a single ignore rule.
+#[derive(Debug, Clone)]
+struct Rule {
+    /// matches a path and captures whatever comes after the matched part
+    pattern: Regex,
+    /// true if the rule excludes paths, false if it is negated
+    excluded: bool,
+    /// true if the rule only matches directories
+    dir_only: bool,
+}
+impl Rule {
+    /// parses a line of an ignore file. Returns `None` for empty lines and
+    /// comments.
+    fn parse(line: &str) -> Option<Rule> {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (excluded, line) = match line.strip_prefix('!') {
+            Some(line) => (false, line),
+            None => (true, line),
+        };
+        let dir_only = line.ends_with('/');
+        let line = line.trim_end_matches('/');
+        let line = match line.strip_prefix('/') {
+            Some(line) => line.to_string(),
+            None => format!("**/{line}"),
+        };
+        if line.is_empty() || line == "**/" {
+            return None;
+        }
+        let pattern =
+            ["^", translate(line.as_str()).as_str(), "(?P<rest>/.*)?$"]
+                .concat();
+        // NOTE: every character that is not a wildcard is escaped, so the
+        // pattern is always a valid regular expression.
+        Some(Rule {
+            pattern: Regex::new(pattern.as_str()).ok()?,
+            excluded,
+            dir_only,
+        })
+    }
+    /// returns true if the rule matches the path
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        match self.pattern.captures(path) {
+            Some(captures) => {
+                // NOTE: directory rules match files below the directory, but
+                // not files that have the same name as the directory.
+                !self.dir_only || is_dir || captures.name("rest").is_some()
+            }
+            None => false,
+        }
+    }
+}
+/// translates a glob pattern into a regular expression
+fn translate(glob: &str) -> String {
+    let mut res = String::new();
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    res.push_str("(.*/)?");
+                } else {
+                    res.push_str(".*");
+                }
+            }
+            '*' => res.push_str("[^/]*"),
+            '?' => res.push_str("[^/]"),
+            c => res.push_str(regex::escape(c.to_string().as_str()).as_str()),
+        }
+    }
+    res
+}
+// ────────────────────────────────────────────────────────────
+/// This struct represents the rules of an ignore file.
+#[derive(Debug, Clone)]
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+impl Default for Rules {
+    /// returns rules that are used when a directory has no ignore file
+    fn default() -> Self {
+        Rules::parse(DEFAULT_RULES)
+    }
+}
+impl Rules {
+    /// parses contents of an ignore file
+    pub fn parse(content: &str) -> Self {
+        Rules {
+            rules: content.lines().filter_map(Rule::parse).collect(),
+        }
+    }
+    /// reads rules of a directory from its ignore file, falling back to the
+    /// default rules if the directory does not have one.
+    pub fn read(root: &Path) -> std::io::Result<Self> {
+        let path: PathBuf = root.join(IGNORE_FILE_NAME);
+        match std::fs::read_to_string(path.as_path()) {
+            Ok(content) => Ok(Rules::parse(content.as_str())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                Ok(Rules::default())
+            }
+            Err(e) => Err(e),
+        }
+    }
+    /// returns true if a path is excluded from the configuration version.
+    ///
+    /// ## Parameters
+    /// * `path` : path relative to the root directory that uses `/` as
+    ///   separator
+    /// * `is_dir` : true if the path is a directory
+    pub fn is_excluded(&self, path: &str, is_dir: bool) -> bool {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| rule.matches(path, is_dir))
+            .map_or(false, |rule| rule.excluded)
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "configuration_versions::ignore::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "configuration_versions::ignore::tests" --nocapture'
+    use super::*;
+    #[test]
+    fn test_default_rules() {
+        let rules = Rules::default();
+        assert!(rules.is_excluded(".git", true));
+        assert!(rules.is_excluded(".git/HEAD", false));
+        assert!(rules.is_excluded("modules/vpc/.git/config", false));
+        assert!(rules.is_excluded(".terraform/providers/x", false));
+        assert!(!rules.is_excluded(".terraform/modules/vpc/main.tf", false));
+        assert!(!rules.is_excluded("main.tf", false));
+        assert!(!rules.is_excluded(".gitignore", false));
+    }
+    #[test]
+    fn test_parse() {
+        let rules = Rules::parse(
+            "
+            # comment
+            *.tfvars
+            !prod.tfvars
+            /docs
+            build/
+            **/cache/*.tmp
+            ",
+        );
+        assert!(rules.is_excluded("dev.tfvars", false));
+        assert!(rules.is_excluded("env/dev.tfvars", false));
+        assert!(!rules.is_excluded("prod.tfvars", false));
+        assert!(rules.is_excluded("docs/index.md", false));
+        assert!(!rules.is_excluded("modules/docs/index.md", false));
+        assert!(rules.is_excluded("modules/build", true));
+        assert!(rules.is_excluded("modules/build/out.zip", false));
+        assert!(!rules.is_excluded("build", false));
+        assert!(rules.is_excluded("a/b/cache/x.tmp", false));
+        assert!(!rules.is_excluded("a/b/cache/x.tf", false));
+        // NOTE: an ignore file replaces the default rules
+        assert!(!rules.is_excluded(".git/HEAD", false));
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
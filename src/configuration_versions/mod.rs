@@ -0,0 +1,462 @@
+//! This module implements the configuration versions API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/configuration-versions).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let configuration_versions = client.configuration_versions();
+//!     let options = tfe_oxide::configuration_versions::CreateOptions {
+//!         auto_queue_runs: Some(true),
+//!         ..Default::default()
+//!     };
+//!     let cv = configuration_versions
+//!         .create("ws-4j8p6jX1w33MiDC7", &options)
+//!         .await
+//!         .unwrap()
+//!         .into_data();
+//!     let upload_url = cv.attributes.unwrap().upload_url.unwrap();
+//!     configuration_versions
+//!         .upload_directory(upload_url.as_str(), "./terraform")
+//!         .await
+//!         .unwrap();
+//! }
+//! ```
+/// This module packs a directory into a gzip-compressed tarball.
+pub mod archive;
+/// This module implements `.terraformignore` rules.
+pub mod ignore;
+// ────────────────────────────────────────────────────────────
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, One, Relationship},
+        pagination::PaginatorOptions,
+    },
+    bytes::Bytes,
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+    std::path::Path,
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a configuration version resource.
+pub type ConfigurationVersion = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single configuration
+/// version.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents possible states of a configuration version.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Pending,
+    Fetching,
+    Uploaded,
+    Archived,
+    Errored,
+    /// a status that is not known to this library
+    #[serde(other)]
+    Unknown,
+}
+/// This struct represents the time a configuration version entered each
+/// status.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct StatusTimestamps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errored_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetching_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+}
+/// This struct represents attributes of a configuration version.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_queue_runs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changed_files: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provisional: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speculative: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_timestamps: Option<StatusTimestamps>,
+    /// pre-signed URL the configuration is uploaded to. It is only returned
+    /// when the configuration version is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_url: Option<String>,
+}
+/// This struct represents relationships of a configuration version.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingress_attributes: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing configuration versions.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of configuration versions per page
+    pub page_size: Option<u32>,
+    /// related resources to include, e.g `ingress_attributes`
+    pub include: Vec<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut res = crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+        ]);
+        if !self.include.is_empty() {
+            res.push(("include".to_string(), self.include.join(",")));
+        }
+        res
+    }
+}
+/// This struct contains options for reading a configuration version.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// related resources to include, e.g `ingress_attributes`
+    pub include: Vec<String>,
+}
+impl ReadOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        if self.include.is_empty() {
+            return Vec::new();
+        }
+        vec![("include".to_string(), self.include.join(","))]
+    }
+}
+/// This struct contains options for creating a configuration version.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    /// queues a run as soon as the configuration is uploaded. Defaults to
+    /// true on the server side.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_queue_runs: Option<bool>,
+    /// only allows speculative plans to use the configuration version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speculative: Option<bool>,
+    /// does not make the configuration version the current one of the
+    /// workspace until a run that uses it is applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provisional: Option<bool>,
+}
+// ────────────────────────────────────────────────────────────
+/// ConfigurationVersions implements the configuration versions API.
+#[derive(Debug, Clone)]
+pub struct ConfigurationVersions<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns configuration versions API
+    pub fn configuration_versions(&self) -> ConfigurationVersions<'_> {
+        ConfigurationVersions { client: self }
+    }
+}
+impl<'a> ConfigurationVersions<'a> {
+    /// lists configuration versions of a workspace
+    pub async fn list(
+        &self,
+        workspace_id: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<ConfigurationVersion>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                format!("workspaces/{workspace_id}/configuration-versions"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns configuration versions of a workspace across all pages. The
+    /// stream ends after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        workspace_id: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<ConfigurationVersion, Error>>, Error>
+    {
+        validate_id("workspace ID", workspace_id)?;
+        Ok(self.client.paginate(
+            &format!("workspaces/{workspace_id}/configuration-versions"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// creates a configuration version in a workspace. Its configuration
+    /// needs to be uploaded to the returned `upload_url` afterwards.
+    pub async fn create(
+        &self,
+        workspace_id: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        let payload = jsonapi::Request::builder("configuration-versions")
+            .attributes(options)
+            .build();
+        self.client
+            .post(
+                format!("workspaces/{workspace_id}/configuration-versions"),
+                None,
+                payload,
+            )
+            .await
+    }
+    /// reads a configuration version
+    pub async fn read(
+        &self,
+        configuration_version_id: &str,
+        options: &ReadOptions,
+    ) -> Result<Response, Error> {
+        validate_id("configuration version ID", configuration_version_id)?;
+        let query = options.to_query();
+        self.client
+            .get(
+                format!("configuration-versions/{configuration_version_id}"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await
+    }
+    /// uploads a gzip-compressed tarball as configuration of a
+    /// configuration version
+    ///
+    /// ## Parameters
+    /// * `upload_url` : `upload_url` attribute of the configuration version
+    /// * `archive` : gzip-compressed tarball
+    pub async fn upload(
+        &self,
+        upload_url: &str,
+        archive: Bytes,
+    ) -> Result<(), Error> {
+        self.client
+            .upload(upload_url, archive::CONTENT_TYPE, archive)
+            .await
+    }
+    /// packs a directory, honoring its `.terraformignore` file, and uploads
+    /// it as configuration of a configuration version.
+    ///
+    /// The archive is written to a temporary file and streamed from there,
+    /// thus the upload is not retried when it fails.
+    ///
+    /// ## Parameters
+    /// * `upload_url` : `upload_url` attribute of the configuration version
+    /// * `path` : directory that contains the configuration
+    ///
+    /// ## Errors
+    /// * `Error::InvalidInput` when the directory contains a symbolic link
+    ///   that points outside of it
+    /// * `Error::Transport` when the directory could not be packed, e.g
+    ///   because it does not exist, or the archive could not be uploaded
+    pub async fn upload_directory<P>(
+        &self,
+        upload_url: &str,
+        path: P,
+    ) -> Result<(), Error>
+    where
+        P: AsRef<Path>,
+    {
+        let path = path.as_ref().to_path_buf();
+        let archive = tokio::task::spawn_blocking(move || {
+            archive::pack_file(path.as_path())
+        })
+        .await
+        .map_err(|e| Error::Transport {
+            reason: format!("failed to pack configuration: {e}"),
+        })?
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::InvalidInput => Error::InvalidInput {
+                reason: format!("failed to pack configuration: {e}"),
+            },
+            _ => Error::Transport {
+                reason: format!("failed to pack configuration: {e}"),
+            },
+        })?;
+        self.client
+            .upload_file(
+                upload_url,
+                archive::CONTENT_TYPE,
+                tokio::fs::File::from_std(archive),
+            )
+            .await
+    }
+    /// downloads configuration of a configuration version as a
+    /// gzip-compressed tarball
+    pub async fn download(
+        &self,
+        configuration_version_id: &str,
+    ) -> Result<Bytes, Error> {
+        validate_id("configuration version ID", configuration_version_id)?;
+        self.client
+            .get_raw(
+                format!(
+                    "configuration-versions/{configuration_version_id}/download"
+                )
+                .as_str(),
+                None,
+            )
+            .await
+    }
+    /// archives a configuration version, which deletes its configuration.
+    /// Configuration versions that are in use can not be archived.
+    pub async fn archive(
+        &self,
+        configuration_version_id: &str,
+    ) -> Result<(), Error> {
+        validate_id("configuration version ID", configuration_version_id)?;
+        self.client
+            .post(
+                format!(
+                    "configuration-versions/{configuration_version_id}/actions/archive"
+                ),
+                None,
+                (),
+            )
+            .await
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "configuration_versions::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "configuration_versions::tests" --nocapture'
+    use {
+        super::*,
+        crate::{
+            core::StatusCode,
+            testing::{Reply, Server},
+        },
+        serde_json::json,
+    };
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/configuration-versions
+        let input = json!({
+            "data": {
+                "id": "cv-UYwHEakurukz85nW",
+                "type": "configuration-versions",
+                "attributes": {
+                    "auto-queue-runs": true,
+                    "error": null,
+                    "error-message": null,
+                    "source": "tfe-api",
+                    "speculative": false,
+                    "status": "pending",
+                    "status-timestamps": {},
+                    "upload-url": "https://archivist.terraform.io/v1/object/9224c6b3-2e14-4cd7-adff-ed484d7294c2"
+                },
+                "relationships": {
+                    "ingress-attributes": {
+                        "data": null,
+                        "links": {
+                            "related": "/api/v2/configuration-versions/cv-UYwHEakurukz85nW/ingress-attributes"
+                        }
+                    }
+                },
+                "links": {
+                    "self": "/api/v2/configuration-versions/cv-UYwHEakurukz85nW",
+                    "download": "/api/v2/configuration-versions/cv-UYwHEakurukz85nW/download"
+                }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.status, Status::Pending);
+        assert_eq!(attributes.auto_queue_runs, Some(true));
+        assert!(attributes.upload_url.is_some());
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_upload_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("main.tf"), "# main").unwrap();
+        let server = Server::start(vec![
+            Reply::status(200, ""),
+            Reply::status(503, "unavailable"),
+        ])
+        .await;
+        let client = server.client();
+        let upload_url = format!("{}/v1/object/upload", server.address());
+        client
+            .configuration_versions()
+            .upload_directory(upload_url.as_str(), dir.path())
+            .await
+            .unwrap();
+        let received = server.received();
+        assert_eq!(received[0].method, "PUT");
+        assert_eq!(received[0].target, "/v1/object/upload");
+        assert_eq!(received[0].headers["content-type"], archive::CONTENT_TYPE);
+        assert_eq!(
+            received[0].headers["content-length"],
+            received[0].body.len().to_string()
+        );
+        assert!(!received[0].headers.contains_key("authorization"));
+        assert_eq!(received[0].body, archive::pack(dir.path()).unwrap());
+        // NOTE: streamed uploads are not retried
+        let actual = client
+            .configuration_versions()
+            .upload_directory(upload_url.as_str(), dir.path())
+            .await
+            .unwrap_err();
+        assert_eq!(actual.status(), Some(StatusCode::SERVICE_UNAVAILABLE));
+        assert_eq!(server.received().len(), 2);
+        let actual = client
+            .configuration_versions()
+            .upload_directory(upload_url.as_str(), dir.path().join("missing"))
+            .await
+            .unwrap_err();
+        assert!(matches!(actual, Error::Transport { .. }));
+        assert_eq!(server.received().len(), 2);
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -1,22 +1,209 @@
-use serde::{Deserialize, Serialize};
+use {
+    crate::jsonapi::Failure,
+    reqwest::StatusCode,
+    serde::{Deserialize, Serialize},
+    std::time::Duration,
+};
 
 /// Custom enum that contains all the possible errors that may occur when making
 /// API requests.
-#[derive(Debug, PartialEq, Eq, thiserror::Error, miette::Diagnostic)]
+#[derive(Debug, PartialEq, thiserror::Error, miette::Diagnostic)]
 pub enum Error {
-    /// The request was made, but the server returned an unsuccessful status
-    /// code, such as 404 or 503. In some cases, the response may contain a
-    /// error detail from the server.
-    #[error("`{canonical_reason}`")]
-    #[diagnostic(code(core::response))]
-    Response {
+    /// The request could not be sent or its response could not be read,
+    /// e.g because of a connection failure.
+    #[error("request failed: {reason}")]
+    #[diagnostic(
+        code(core::transport),
+        help("check network connectivity and the API address")
+    )]
+    Transport {
         /// short description of the error
-        canonical_reason: String,
+        reason: String,
+    },
+    /// The request or an operation waiting on the server did not complete
+    /// in time.
+    #[error("timed out: {reason}")]
+    #[diagnostic(
+        code(core::timeout),
+        help("increase the timeout or retry the operation later")
+    )]
+    Timeout {
+        /// short description of the error
+        reason: String,
+    },
+    /// The server returned an unsuccessful status code, such as 503, with a
+    /// body that is not a JSON:API error document.
+    #[error("server responded with `{status}`")]
+    #[diagnostic(
+        code(core::status),
+        help("the response body may contain more details")
+    )]
+    Status {
         /// HTTP status code
-        status: Option<String>,
+        status: StatusCode,
         /// Complete JSON response from the server
         body: Option<serde_json::Value>,
     },
+    /// The server returned an unsuccessful status code along with a JSON:API
+    /// error document, e.g a 422 response for invalid attributes.
+    #[error("server responded with `{status}`. {failure}")]
+    #[diagnostic(
+        code(core::failure),
+        help(
+            "`source.pointer` of each error points to the invalid attribute"
+        )
+    )]
+    Failure {
+        /// HTTP status code
+        status: StatusCode,
+        /// errors returned by the server
+        failure: Failure,
+    },
+    /// A server response could not be converted into the expected type.
+    #[error("failed to deserialize server response at `{path}`: {reason}")]
+    #[diagnostic(
+        code(core::deserialize),
+        help("the API may have changed; please report this issue")
+    )]
+    Deserialize {
+        /// path of the value that failed, e.g `data.attributes.status`
+        path: String,
+        /// short description of the error
+        reason: String,
+    },
+    /// The API token is missing, invalid or expired.
+    #[error("unauthorized")]
+    #[diagnostic(
+        code(core::unauthorized),
+        help("check that the API token is valid and has not expired")
+    )]
+    Unauthorized {
+        /// errors returned by the server
+        failure: Option<Failure>,
+    },
+    /// The resource does not exist, or the API token is not allowed to see
+    /// it.
+    #[error("not found")]
+    #[diagnostic(
+        code(core::not_found),
+        help("check the ID and that the API token can access the resource")
+    )]
+    NotFound {
+        /// errors returned by the server
+        failure: Option<Failure>,
+    },
+    /// Too many requests were sent with the API token.
+    #[error("rate limited")]
+    #[diagnostic(
+        code(core::rate_limited),
+        help("wait for `retry_after` before sending the next request")
+    )]
+    RateLimited {
+        /// time the server asked to wait before retrying
+        retry_after: Option<Duration>,
+        /// errors returned by the server
+        failure: Option<Failure>,
+    },
+    /// An argument or an option is not valid. The request was not sent.
+    #[error("invalid input: {reason}")]
+    #[diagnostic(
+        code(core::invalid_input),
+        help("check the arguments passed to the method")
+    )]
+    InvalidInput {
+        /// short description of the error
+        reason: String,
+    },
+}
+// ────────────────────────────────────────────────────────────
+impl Error {
+    /// returns HTTP status code of the server response, if any
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Status { status, .. } | Error::Failure { status, .. } => {
+                Some(*status)
+            }
+            Error::Unauthorized { .. } => Some(StatusCode::UNAUTHORIZED),
+            Error::NotFound { .. } => Some(StatusCode::NOT_FOUND),
+            Error::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            _ => None,
+        }
+    }
+    /// returns errors the server responded with, if any. Each error tells
+    /// which value of the request document caused it.
+    pub fn failure(&self) -> Option<&Failure> {
+        match self {
+            Error::Failure { failure, .. } => Some(failure),
+            Error::Unauthorized { failure }
+            | Error::NotFound { failure }
+            | Error::RateLimited { failure, .. } => failure.as_ref(),
+            _ => None,
+        }
+    }
+    /// returns time the server asked to wait before retrying, if any
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            Error::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+    /// returns true if the request could not be sent or its response could
+    /// not be read
+    pub fn is_transport(&self) -> bool {
+        matches!(self, Error::Transport { .. })
+    }
+    /// returns true if the request did not complete in time
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, Error::Timeout { .. })
+    }
+    /// returns true if a server response could not be deserialized
+    pub fn is_deserialize(&self) -> bool {
+        matches!(self, Error::Deserialize { .. })
+    }
+    /// returns true if the API token is missing, invalid or expired
+    pub fn is_unauthorized(&self) -> bool {
+        matches!(self, Error::Unauthorized { .. })
+    }
+    /// returns true if the resource does not exist
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, Error::NotFound { .. })
+    }
+    /// returns true if too many requests were sent
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self, Error::RateLimited { .. })
+    }
+    /// returns true if an argument or an option is not valid
+    pub fn is_invalid_input(&self) -> bool {
+        matches!(self, Error::InvalidInput { .. })
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "core::errors::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "core::errors::tests" --nocapture'
+    use super::*;
+    #[test]
+    fn test_predicates() {
+        let e = Error::NotFound { failure: None };
+        assert!(e.is_not_found());
+        assert!(!e.is_unauthorized());
+        assert_eq!(e.status(), Some(StatusCode::NOT_FOUND));
+        let e = Error::RateLimited {
+            retry_after: Some(Duration::from_secs(2)),
+            failure: None,
+        };
+        assert!(e.is_rate_limited());
+        assert_eq!(e.retry_after(), Some(Duration::from_secs(2)));
+        let e = Error::InvalidInput {
+            reason: "invalid value for workspace ID: `a/b`".to_string(),
+        };
+        assert!(e.is_invalid_input());
+        assert_eq!(e.status(), None);
+        assert_eq!(
+            e.to_string(),
+            "invalid input: invalid value for workspace ID: `a/b`"
+        );
+    }
 }
 // ────────────────────────────────────────────────────────────
 // vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
@@ -4,20 +4,19 @@
 //!
 //! Basic usage:
 //!
-//! ```
-//! [tokio::main]
+//! ```no_run
+//! use tfe_oxide::core::HttpClient;
+//!
+//! #[tokio::main]
 //! async fn main() {
-//!     let builder = crate::core::ReqwestClientBuilder::new();
+//!     let builder = tfe_oxide::core::ReqwestClientBuilder::new();
 //!     let client = builder.build().unwrap();
-//!     let response: Result<serde_json::Value, crate::core::Error> =
+//!     let response: Result<serde_json::Value, tfe_oxide::core::Error> =
 //!         client.get("wrongurl", None, None).await;
 //!     match response {
 //!         Ok(data) => println!("request succeeded: {:?}", data),
-//!         Err(crate::core::Error::Response {
-//!             canonical_reason, ..
-//!         }) => {
-//!             eprintln!("request failed: {}", canonical_reason)
-//!         }
+//!         Err(e) if e.is_not_found() => eprintln!("resource not found"),
+//!         Err(e) => eprintln!("request failed: {}", e),
 //!     };
 //! }
 //! ```
@@ -26,9 +25,14 @@
 /// making API calls.
 // TODO: maybe this should be private
 pub mod errors;
+/// This module implements client-side rate limiting and retries of rate
+/// limited requests.
+pub mod rate_limit;
 /// This module implements HttpClient trait using reqwest
 // TODO: maybe this should be private
 pub mod reqwest;
+/// This module implements a type that holds sensitive values.
+pub mod secret;
 // ────────────────────────────────────────────────────────────
 use {
     serde::{de::DeserializeOwned, Serialize},
@@ -38,15 +42,40 @@ use {
 // ────────────────────────────────────────────────────────────
 /// This represents a error in this library.
 pub type Error = errors::Error;
+/// This represents HTTP status code of a server response.
+pub type StatusCode = ::reqwest::StatusCode;
+/// This represents a sensitive value that is wiped from memory when dropped.
+pub type Secret = secret::Secret;
 /// This represents client headers.
 pub type Headers = std::collections::HashMap<String, String>;
 /// This represents query section of an HTTP request.
 pub type Query<'a> = std::collections::HashMap<&'a str, &'a str>;
 /// This represents Reqwest client implementation of HttpClient trait
 pub type ReqwestClient = crate::core::reqwest::Client;
+/// This represents the request quota reported by the API.
+pub type RateLimit = rate_limit::RateLimit;
 /// This is used to build a Reqwest client.
 pub type ReqwestClientBuilder = crate::core::reqwest::Builder;
 // ────────────────────────────────────────────────────────────
+/// converts owned query parameters into `Query`
+pub(crate) fn query(params: &[(String, String)]) -> Query<'_> {
+    params
+        .iter()
+        .map(|(key, value)| (key.as_str(), value.as_str()))
+        .collect()
+}
+/// returns owned query parameters, in order, leaving out the ones whose
+/// value is unset
+pub(crate) fn query_params<'a, I>(params: I) -> Vec<(String, String)>
+where
+    I: IntoIterator<Item = (&'a str, Option<String>)>,
+{
+    params
+        .into_iter()
+        .filter_map(|(key, value)| Some((key.to_string(), value?)))
+        .collect()
+}
+// ────────────────────────────────────────────────────────────
 /// This trait represents the interface to be implemented for an HTTP client,
 /// which is kept separate from the implementation.
 ///
@@ -0,0 +1,368 @@
+use {
+    getset::Getters,
+    reqwest::{
+        header::{HeaderMap, RETRY_AFTER},
+        Request, Response, StatusCode,
+    },
+    reqwest_middleware::{Middleware, Next},
+    reqwest_retry::{
+        default_on_request_failure, default_on_request_success, Retryable,
+        RetryableStrategy,
+    },
+    std::{
+        sync::{Arc, Mutex},
+        time::{Duration, Instant, SystemTime},
+    },
+    task_local_extensions::Extensions,
+};
+/// Default values
+pub(crate) const DEFAULT_REQUESTS_PER_SECOND: u32 = 30;
+pub(crate) const DEFAULT_MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+/// time to wait when a `429` response does not tell how long to wait
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(1);
+/// Rate limit headers sent by the API
+const RATE_LIMIT_LIMIT: &str = "x-ratelimit-limit";
+const RATE_LIMIT_REMAINING: &str = "x-ratelimit-remaining";
+const RATE_LIMIT_RESET: &str = "x-ratelimit-reset";
+// ────────────────────────────────────────────────────────────
+/// This struct represents the request quota of the API token, as reported by
+/// the `X-RateLimit-*` headers of the last response.
+#[derive(Clone, Debug, PartialEq, Getters)]
+#[getset(get = "pub with_prefix")]
+pub struct RateLimit {
+    /// number of requests allowed per second
+    limit: Option<u32>,
+    /// number of requests left in the current window
+    remaining: Option<u32>,
+    /// time until the current window resets
+    reset: Option<Duration>,
+}
+impl RateLimit {
+    /// returns the quota reported by response headers, if any of the
+    /// `X-RateLimit-*` headers is present
+    pub fn from_headers(headers: &HeaderMap) -> Option<Self> {
+        let limit = header(headers, RATE_LIMIT_LIMIT)
+            .and_then(|v| v.parse::<u32>().ok());
+        let remaining = header(headers, RATE_LIMIT_REMAINING)
+            .and_then(|v| v.parse::<u32>().ok());
+        let reset = header(headers, RATE_LIMIT_RESET).and_then(seconds);
+        if limit.is_none() && remaining.is_none() && reset.is_none() {
+            return None;
+        }
+        Some(Self {
+            limit,
+            remaining,
+            reset,
+        })
+    }
+}
+/// returns the trimmed value of a header, if it is valid ASCII
+fn header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers.get(name)?.to_str().ok().map(str::trim)
+}
+/// parses a non-negative, possibly fractional, number of seconds. Values
+/// that do not fit in a `Duration`, e.g `1e300`, are ignored.
+fn seconds(value: &str) -> Option<Duration> {
+    // NOTE: `Duration::from_secs_f64` panics on values that do not fit,
+    // and `Duration::try_from_secs_f64` is newer than the supported Rust
+    // version
+    let value = value.parse::<f64>().ok()?;
+    (value.is_finite() && value >= 0.0 && value < u64::MAX as f64)
+        .then(|| Duration::from_secs_f64(value))
+}
+/// parses an HTTP-date, e.g `Wed, 21 Oct 2015 07:28:00 GMT`, and returns the
+/// time left until then. Dates in the past are zero.
+fn http_date(value: &str) -> Option<Duration> {
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+/// returns how long the server asked to wait before retrying, from the
+/// `Retry-After` header or, failing that, the `X-RateLimit-Reset` header.
+/// `Retry-After` is either a number of seconds or an HTTP-date, while
+/// `X-RateLimit-Reset` is a number of seconds.
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    header(headers, RETRY_AFTER.as_str())
+        .and_then(|v| seconds(v).or_else(|| http_date(v)))
+        .or_else(|| header(headers, RATE_LIMIT_RESET).and_then(seconds))
+}
+// ────────────────────────────────────────────────────────────
+/// This struct implements a token bucket that allows `capacity` requests in
+/// a burst and refills at `rate` requests per second.
+#[derive(Clone, Debug)]
+struct TokenBucket {
+    capacity: f64,
+    rate: f64,
+    tokens: f64,
+    updated: Instant,
+}
+impl TokenBucket {
+    /// creates a full bucket
+    fn new(requests_per_second: u32, now: Instant) -> Self {
+        let rate = f64::from(requests_per_second);
+        Self {
+            capacity: rate,
+            rate,
+            tokens: rate,
+            updated: now,
+        }
+    }
+    /// takes a token and returns how long to wait before sending the
+    /// request. Tokens are taken even when the bucket is empty, so that
+    /// concurrent requests are queued rather than sent at once when the
+    /// bucket refills.
+    fn acquire(&mut self, now: Instant) -> Duration {
+        let elapsed = now.saturating_duration_since(self.updated);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.rate)
+            .min(self.capacity);
+        self.updated = now;
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            return Duration::ZERO;
+        }
+        Duration::from_secs_f64(-self.tokens / self.rate)
+    }
+}
+// ────────────────────────────────────────────────────────────
+/// RateLimiter is a middleware that keeps requests under the rate limit of
+/// the API and retries rate limited requests after the time the server asks
+/// to wait.
+///
+/// It also records the quota reported by the last response, which is
+/// returned by `RateLimiter::get_rate_limit`. Clones share their state, so
+/// the quota can be read from a clone of the middleware that was added to
+/// the client.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimiter {
+    /// client-side limiter. Requests are not throttled when it is `None`.
+    bucket: Option<Arc<Mutex<TokenBucket>>>,
+    /// quota reported by the last response
+    rate_limit: Arc<Mutex<Option<RateLimit>>>,
+    /// maximum number of times a rate limited request is retried
+    max_retries: u32,
+    /// maximum waiting time before retrying a rate limited request, which
+    /// caps the time the server asks to wait
+    max_retry_after: Duration,
+}
+impl RateLimiter {
+    /// creates a middleware that sends at most `requests_per_second`
+    /// requests per second, or does not throttle requests if it is `0`, and
+    /// retries rate limited requests up to `max_retries` times, waiting at
+    /// most `max_retry_after` before each retry.
+    pub fn new(
+        requests_per_second: u32,
+        max_retries: u32,
+        max_retry_after: Duration,
+    ) -> Self {
+        let bucket = (requests_per_second > 0).then(|| {
+            Arc::new(Mutex::new(TokenBucket::new(
+                requests_per_second,
+                Instant::now(),
+            )))
+        });
+        Self {
+            bucket,
+            rate_limit: Arc::default(),
+            max_retries,
+            max_retry_after,
+        }
+    }
+    /// returns the quota reported by the last response, if the server sent
+    /// `X-RateLimit-*` headers
+    pub fn get_rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit.lock().ok()?.clone()
+    }
+    /// waits until the client-side limiter allows sending a request
+    async fn acquire(&self) {
+        let wait = match &self.bucket {
+            // NOTE: the lock is released before sleeping
+            Some(bucket) => match bucket.lock() {
+                Ok(mut bucket) => bucket.acquire(Instant::now()),
+                Err(_) => Duration::ZERO,
+            },
+            None => Duration::ZERO,
+        };
+        if !wait.is_zero() {
+            tracing::debug!("\nthrottling request for {:?}", wait);
+            tokio::time::sleep(wait).await;
+        }
+    }
+    /// records the quota reported by response headers
+    fn record(&self, headers: &HeaderMap) {
+        if let Some(rate_limit) = RateLimit::from_headers(headers) {
+            if let Ok(mut v) = self.rate_limit.lock() {
+                *v = Some(rate_limit);
+            }
+        }
+    }
+}
+#[async_trait::async_trait]
+impl Middleware for RateLimiter {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut req = req;
+        let mut attempt = 0;
+        loop {
+            self.acquire().await;
+            // NOTE: requests with a streamed body cannot be cloned, thus
+            // they are not retried
+            let duplicate = req.try_clone();
+            let response = next.clone().run(req, extensions).await?;
+            self.record(response.headers());
+            if response.status() != StatusCode::TOO_MANY_REQUESTS
+                || attempt >= self.max_retries
+            {
+                return Ok(response);
+            }
+            let Some(duplicate) = duplicate else {
+                return Ok(response);
+            };
+            let wait = retry_after(response.headers())
+                .unwrap_or(DEFAULT_RETRY_AFTER)
+                .min(self.max_retry_after);
+            tracing::warn!(
+                "\nrequest was rate limited; retrying in {:?} ({}/{})",
+                wait,
+                attempt + 1,
+                self.max_retries
+            );
+            tokio::time::sleep(wait).await;
+            req = duplicate;
+            attempt += 1;
+        }
+    }
+}
+// ────────────────────────────────────────────────────────────
+/// This struct tells the retry middleware which requests to retry. It
+/// behaves as the default strategy of `reqwest_retry`, except that `429`
+/// responses are left to `RateLimiter`, which waits as long as the server
+/// asks rather than backing off exponentially.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RetryStrategy;
+impl RetryableStrategy for RetryStrategy {
+    fn handle(
+        &self,
+        res: &reqwest_middleware::Result<Response>,
+    ) -> Option<Retryable> {
+        match res {
+            Ok(response)
+                if response.status() == StatusCode::TOO_MANY_REQUESTS =>
+            {
+                Some(Retryable::Fatal)
+            }
+            Ok(response) => default_on_request_success(response),
+            Err(e) => default_on_request_failure(e),
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "core::rate_limit::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "core::rate_limit::tests" --nocapture'
+    use {super::*, reqwest::header::HeaderValue};
+    #[test]
+    fn test_token_bucket() {
+        let now = Instant::now();
+        let mut bucket = TokenBucket::new(2, now);
+        assert_eq!(bucket.acquire(now), Duration::ZERO);
+        assert_eq!(bucket.acquire(now), Duration::ZERO);
+        // NOTE: the bucket is empty, so the next requests are queued
+        assert_eq!(bucket.acquire(now), Duration::from_millis(500));
+        assert_eq!(bucket.acquire(now), Duration::from_secs(1));
+        let later = now + Duration::from_secs(1);
+        assert_eq!(bucket.acquire(later), Duration::from_millis(500));
+        // NOTE: tokens do not accumulate past the capacity
+        let later = later + Duration::from_secs(10);
+        assert_eq!(bucket.acquire(later), Duration::ZERO);
+        assert_eq!(bucket.acquire(later), Duration::ZERO);
+        assert_eq!(bucket.acquire(later), Duration::from_millis(500));
+    }
+    #[test]
+    fn test_rate_limit_from_headers() {
+        assert_eq!(RateLimit::from_headers(&HeaderMap::new()), None);
+        let mut headers = HeaderMap::new();
+        headers.insert(RATE_LIMIT_LIMIT, HeaderValue::from_static("30"));
+        headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from_static("29"));
+        headers.insert(RATE_LIMIT_RESET, HeaderValue::from_static("0.25"));
+        let actual = RateLimit::from_headers(&headers).unwrap();
+        assert_eq!(actual.get_limit(), &Some(30));
+        assert_eq!(actual.get_remaining(), &Some(29));
+        assert_eq!(actual.get_reset(), &Some(Duration::from_millis(250)));
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(250)));
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("2"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(2)));
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), Some(Duration::ZERO));
+        let date = httpdate::fmt_http_date(
+            SystemTime::now() + Duration::from_secs(120),
+        );
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(&date).unwrap());
+        let actual = retry_after(&headers).unwrap();
+        assert!(actual > Duration::from_secs(100));
+        assert!(actual <= Duration::from_secs(120));
+    }
+    #[test]
+    fn test_retry_after_out_of_range() {
+        // NOTE: regression test, these values used to panic
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("1e300"));
+        headers.insert(RATE_LIMIT_RESET, HeaderValue::from_static("1e300"));
+        assert_eq!(retry_after(&headers), None);
+        assert_eq!(RateLimit::from_headers(&headers), None);
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("-1"));
+        headers.insert(RATE_LIMIT_RESET, HeaderValue::from_static("NaN"));
+        assert_eq!(retry_after(&headers), None);
+        headers.insert(RATE_LIMIT_RESET, HeaderValue::from_static("0.5"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(500)));
+    }
+    #[test]
+    fn test_rate_limiter() {
+        let limiter = RateLimiter::new(0, 3, DEFAULT_MAX_RETRY_AFTER);
+        assert!(limiter.bucket.is_none());
+        assert_eq!(limiter.get_rate_limit(), None);
+        let mut headers = HeaderMap::new();
+        headers.insert(RATE_LIMIT_REMAINING, HeaderValue::from_static("0"));
+        // NOTE: clones share the recorded quota
+        limiter.clone().record(&headers);
+        assert_eq!(
+            limiter.get_rate_limit().and_then(|v| *v.get_remaining()),
+            Some(0)
+        );
+    }
+    #[tokio::test]
+    async fn test_rate_limiter_caps_retry_after() {
+        use crate::{
+            core::{HttpClient, ReqwestClientBuilder},
+            testing::{Reply, Server},
+        };
+        let server = Server::start(vec![
+            Reply::status(429, "{}").header("retry-after", "100000"),
+            Reply::json(serde_json::json!({ "ok": true })),
+        ])
+        .await;
+        let client = ReqwestClientBuilder::new()
+            .set_max_retry_after(10)
+            .unwrap()
+            .build()
+            .unwrap();
+        let started = Instant::now();
+        let url = format!("{}/api/v2/ping", server.address());
+        let actual: serde_json::Value =
+            client.get(url, None, None).await.unwrap();
+        assert_eq!(actual, serde_json::json!({ "ok": true }));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert_eq!(server.received().len(), 2);
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
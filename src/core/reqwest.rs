@@ -1,10 +1,16 @@
 use {
+    super::rate_limit::{
+        retry_after, RateLimit, RateLimiter, RetryStrategy,
+        DEFAULT_MAX_RETRY_AFTER, DEFAULT_REQUESTS_PER_SECOND,
+    },
+    crate::jsonapi::Failure,
+    bytes::Bytes,
     getset::{Getters, Setters},
     maybe_async::async_impl,
     reqwest::header::{
         HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE, USER_AGENT,
     },
-    reqwest::Method,
+    reqwest::{Method, StatusCode},
     reqwest_middleware::ClientBuilder,
     reqwest_middleware::ClientWithMiddleware,
     reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware},
@@ -24,51 +30,105 @@ const DEFAULT_TIMEOUT: u64 = 30 * 1000;
 const DEFAULT_MIN_RETRY_INTERVAL: u64 = 100;
 const DEFAULT_MAX_RETRY_INTERVAL: u64 = 5 * 1000;
 const DEFAULT_MAX_RETRIES: u32 = 3;
+const DEFAULT_WAIT_TIMEOUT: u64 = 60 * 60 * 1000;
 // ────────────────────────────────────────────────────────────
 #[derive(Debug, Clone)]
 /// Client is a client that uses the reqwest crate to make HTTP requests.
 // NOTE: do not implement Deref traits as deref coercion would prevent Client
 // methods to get called
-pub struct Client(ClientWithMiddleware);
+pub struct Client(
+    ClientWithMiddleware,
+    RateLimiter,
+    /// client without the retry middleware, which sends requests whose body
+    /// is streamed as they cannot be retried
+    Option<ClientWithMiddleware>,
+);
 
 // ────────────────────────────────────────────────────────────
 #[allow(dead_code)]
 impl Client {
     /// creates a new api client that is essentially a wrapper around reqwest_middleware::ClientWithMiddleware
+    ///
+    /// NOTE: the client does not report the rate limit quota unless it is
+    /// created with `Client::with_rate_limiter`.
     pub fn new(client: ClientWithMiddleware) -> Self {
+        Self::with_rate_limiter(client, RateLimiter::default())
+    }
+    /// creates a new api client whose rate limit quota is read from the given
+    /// middleware, which is expected to be part of the middleware stack of
+    /// `client`
+    pub fn with_rate_limiter(
+        client: ClientWithMiddleware,
+        rate_limiter: RateLimiter,
+    ) -> Self {
         let span: tracing::Span =
             tracing::span!(tracing::Level::INFO, "Client");
         let _guard = span.enter();
         let span: tracing::Span = tracing::span!(tracing::Level::INFO, "new");
         let _guard = span.enter();
 
-        Client(client)
+        Client(client, rate_limiter, None)
     }
-    /// executes the actual HTTP request
+    /// sets the client that sends requests whose body is streamed, which is
+    /// expected not to retry requests
+    fn with_streaming_client(self, client: ClientWithMiddleware) -> Self {
+        Client(self.0, self.1, Some(client))
+    }
+    /// returns the request quota reported by the `X-RateLimit-*` headers of
+    /// the last response, if any
+    pub fn get_rate_limit(&self) -> Option<RateLimit> {
+        self.1.get_rate_limit()
+    }
+    /// sends the actual HTTP request and returns server response without
+    /// reading its body
     ///
     /// ## Parameters
     /// * `method` : Http Verb
-    /// * `endpoint` : url suffix that get's appended to the base url that was set in client builder to form the final endpoint address http request is sent to
+    /// * `url` : address the http request is sent to
     /// * `headers` : optional headers to add to the request
     /// * `mutator_fn` : a function that allows changing the underlying request builder (e.g add data)
-    async fn exec<T, R>(
+    async fn send<T>(
         &self,
         method: reqwest::Method,
         url: &str,
         headers: Option<&super::Headers>,
         mutator_fn: T,
-    ) -> miette::Result<serde_json::Value, super::Error>
+    ) -> miette::Result<reqwest::Response, super::Error>
     where
-        R: DeserializeOwned + Serialize + Debug,
-        T: Fn(
+        T: FnOnce(
             reqwest_middleware::RequestBuilder,
         ) -> reqwest_middleware::RequestBuilder,
     {
-        let span: tracing::Span = tracing::span!(tracing::Level::INFO, "exec");
+        self.send_with(&self.0, method, url, headers, mutator_fn)
+            .await
+    }
+    /// sends the actual HTTP request with the given client and returns
+    /// server response without reading its body
+    ///
+    /// ## Parameters
+    /// * `client` : client that sends the request
+    /// * `method` : Http Verb
+    /// * `url` : address the http request is sent to
+    /// * `headers` : optional headers to add to the request
+    /// * `mutator_fn` : a function that allows changing the underlying request builder (e.g add data)
+    async fn send_with<T>(
+        &self,
+        client: &ClientWithMiddleware,
+        method: reqwest::Method,
+        url: &str,
+        headers: Option<&super::Headers>,
+        mutator_fn: T,
+    ) -> miette::Result<reqwest::Response, super::Error>
+    where
+        T: FnOnce(
+            reqwest_middleware::RequestBuilder,
+        ) -> reqwest_middleware::RequestBuilder,
+    {
+        let span: tracing::Span = tracing::span!(tracing::Level::INFO, "send");
         let _guard = span.enter();
         tracing::info!("\nsending request to {}", url);
         // let url = [self.url_prefix.as_str(), "/", endpoint.as_ref()].concat();
-        let request = self.0.request(method, url);
+        let request = client.request(method, url);
         let request = match headers {
             Some(headers) => {
                 // tracing::debug!(
@@ -83,17 +143,11 @@ impl Client {
                 // condition cannot be broken by the user and will always be
                 // true.
 
-                let headers: reqwest::header::HeaderMap = headers
-                    .try_into()
-                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error>)
-                    .map_err(|e| {
-                        let e = super::Error::Response {
-                            canonical_reason: e.to_string(),
-                            status: None,
-                            body: None,
-                        };
-                        // tracing::error!("\n{:?}", &e);
-                        return e;
+                let headers: reqwest::header::HeaderMap =
+                    headers.try_into().map_err(|e| {
+                        super::Error::InvalidInput {
+                            reason: format!("invalid request headers. {e}"),
+                        }
                     })?;
                 // NOTE: alternatively, the following could have been used but in that case, we wouldn't handled the error
                 // let headers = headers.try_into().unwrap();
@@ -111,19 +165,31 @@ impl Client {
         // tracing::trace!("\nMaking request {:?}", request);
         // ─────────────────────────────────────────────────────────────────────────────
         tracing::debug!("\nsending HTTP Request");
-        let response =
-            request
-                .send()
-                .await
-                .map_err(|e: reqwest_middleware::Error| {
-                    let e = super::Error::Response {
-                        canonical_reason: e.to_string(),
-                        status: None,
-                        body: None,
-                    };
-                    // tracing::error!("\n{:?}", &e);
-                    return e;
-                })?;
+        let response = request.send().await.map_err(transport_error)?;
+        Ok(response)
+    }
+    /// executes the actual HTTP request and returns server response as JSON
+    ///
+    /// ## Parameters
+    /// * `method` : Http Verb
+    /// * `url` : address the http request is sent to
+    /// * `headers` : optional headers to add to the request
+    /// * `mutator_fn` : a function that allows changing the underlying request builder (e.g add data)
+    async fn exec<T>(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        headers: Option<&super::Headers>,
+        mutator_fn: T,
+    ) -> miette::Result<serde_json::Value, super::Error>
+    where
+        T: FnOnce(
+            reqwest_middleware::RequestBuilder,
+        ) -> reqwest_middleware::RequestBuilder,
+    {
+        let span: tracing::Span = tracing::span!(tracing::Level::INFO, "exec");
+        let _guard = span.enter();
+        let response = self.send(method, url, headers, mutator_fn).await?;
         tracing::debug!("\nconverting server response to JSON");
         let status = response.status();
         // ─────────────────────────────────────────────────────────────────────────────
@@ -134,62 +200,255 @@ impl Client {
         //     .json::<serde_json::Value>()
         //     .await
         //     .map_err(|e: reqwest::Error| {
-        //         let e = super::Error::Response {
-        //             canonical_reason: e.to_string(),
-        //             status: None,
-        //             body: None,
+        //         let e = super::Error::Transport {
+        //             reason: e.to_string(),
         //         };
         //         // tracing::error!("\n{:?}", &e);
-        //         return e;
+        //         e
         //     })?;
         // ─────────────────────────────────────────────────────────────────────────────
-        let response =
-            response.text().await.map_err(|e: reqwest::Error| {
-                let e = super::Error::Response {
-                    canonical_reason: e.to_string(),
-                    status: None,
-                    body: None,
-                };
-                return e;
-            })?;
-        let response: serde_json::Value = serde_json::from_str(
-            response.as_str(),
-        )
-        .map_err(|e: serde_json::Error| {
-            let e = super::Error::Response {
-                canonical_reason: format!(
-                    "corrupted response JSON payload received. {}",
-                    e.to_string()
-                ),
-                status: Some(
-                    reqwest::StatusCode::BAD_REQUEST.as_str().to_string(),
-                ),
-                body: None,
+        let headers = response.headers().clone();
+        let response = response.text().await.map_err(reqwest_error)?;
+        // NOTE: some endpoints, e.g `DELETE` ones, respond with
+        // `204 No Content`
+        if response.trim().is_empty() {
+            tracing::debug!("\nserver response has an empty body");
+            if !status.is_success() {
+                return Err(status_error(status, &headers, None));
+            }
+            return Ok(serde_json::Value::Null);
+        }
+        let response: serde_json::Value =
+            match serde_json::from_str(response.as_str()) {
+                Ok(response) => response,
+                // NOTE: error pages of proxies are usually not JSON
+                Err(_) if !status.is_success() => {
+                    return Err(status_error(status, &headers, None));
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "\nreceived {} bytes of corrupted JSON",
+                        response.len()
+                    );
+                    return Err(super::Error::Deserialize {
+                        path: ".".to_string(),
+                        reason: format!(
+                            "corrupted response JSON payload received. {e}"
+                        ),
+                    });
+                }
             };
-            tracing::error!("\nraw faulty response:\n{:?}", response);
-            return e;
-        })?;
-
-        tracing::trace!(
-            "Response Raw Data:\n{}",
-            serde_json::to_string_pretty(&response).unwrap()
-        );
+        // SECURITY: the response body is not logged as it may contain
+        // secrets, e.g tokens returned when they are created
+        tracing::trace!("\nreceived JSON response with status {}", status);
         if !status.is_success() {
-            let e = super::Error::Response {
-                canonical_reason: status
-                    .canonical_reason()
-                    .unwrap_or("server returned an error response")
-                    .to_string(),
-                status: Some(status.as_str().to_string()),
-                body: Some(response),
-            };
-            // tracing::error!("\n{:?}", &e);
-            return Err(e);
+            return Err(status_error(status, &headers, Some(response)));
         }
         Ok(response)
     }
 }
 
+#[allow(dead_code)]
+impl Client {
+    /// sends GET request and returns server response body as raw bytes. It is
+    /// used for endpoints that do not return JSON, e.g logs.
+    ///
+    /// ## Parameters
+    /// * `url` : address the http request is sent to
+    /// * `headers` : optional headers to add to the request
+    /// * `payload` : optional query parameters
+    pub async fn get_bytes<S>(
+        &self,
+        url: S,
+        headers: Option<&super::Headers>,
+        payload: Option<&super::Query<'_>>,
+    ) -> Result<Bytes, super::Error>
+    where
+        S: AsRef<str> + Sync + Send,
+    {
+        let span: tracing::Span =
+            tracing::span!(tracing::Level::INFO, "get_bytes");
+        let _guard = span.enter();
+        let response = self
+            .send(Method::GET, url.as_ref(), headers, |req| match payload {
+                Some(payload) => req.query(payload),
+                None => req,
+            })
+            .await?;
+        bytes_response(response).await
+    }
+    /// sends PUT request with a raw body, e.g an archive, and ignores the
+    /// server response body.
+    ///
+    /// ## Parameters
+    /// * `url` : address the http request is sent to
+    /// * `headers` : optional headers to add to the request. A `Content-Type`
+    ///   header overrides the JSON content type the client sends by default.
+    /// * `body` : request body
+    pub async fn put_bytes<S>(
+        &self,
+        url: S,
+        headers: Option<&super::Headers>,
+        body: Bytes,
+    ) -> Result<(), super::Error>
+    where
+        S: AsRef<str> + Sync + Send,
+    {
+        let span: tracing::Span =
+            tracing::span!(tracing::Level::INFO, "put_bytes");
+        let _guard = span.enter();
+        tracing::debug!("\nsending {} bytes", body.len());
+        // NOTE: the body is already in memory, so cloning it to retry the
+        // request is cheap. Large bodies should be sent with `put_stream`.
+        let response = self
+            .send(Method::PUT, url.as_ref(), headers, |req| {
+                req.body(reqwest::Body::from(body))
+            })
+            .await?;
+        bytes_response(response).await?;
+        Ok(())
+    }
+    /// sends PUT request with a streamed body, e.g an archive read from a
+    /// file, and ignores the server response body.
+    ///
+    /// The body is not kept in memory, thus the request is not retried when
+    /// it fails.
+    ///
+    /// ## Parameters
+    /// * `url` : address the http request is sent to
+    /// * `headers` : optional headers to add to the request. A `Content-Type`
+    ///   header overrides the JSON content type the client sends by default.
+    /// * `body` : request body
+    /// * `length` : length of the body in bytes, which is sent as the
+    ///   `Content-Length` header since pre-signed URLs usually reject chunked
+    ///   bodies
+    pub async fn put_stream<S>(
+        &self,
+        url: S,
+        headers: Option<&super::Headers>,
+        body: reqwest::Body,
+        length: u64,
+    ) -> Result<(), super::Error>
+    where
+        S: AsRef<str> + Sync + Send,
+    {
+        let span: tracing::Span =
+            tracing::span!(tracing::Level::INFO, "put_stream");
+        let _guard = span.enter();
+        tracing::debug!("\nstreaming {} bytes", length);
+        let client = self.2.as_ref().unwrap_or(&self.0);
+        let response = self
+            .send_with(client, Method::PUT, url.as_ref(), headers, |req| {
+                req.header(reqwest::header::CONTENT_LENGTH, length)
+                    .body(body)
+            })
+            .await?;
+        bytes_response(response).await?;
+        Ok(())
+    }
+}
+/// reads server response body as raw bytes, returning an error if the
+/// response status is not successful
+async fn bytes_response(
+    response: reqwest::Response,
+) -> Result<Bytes, super::Error> {
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.bytes().await.map_err(reqwest_error)?;
+    tracing::debug!("\nreceived {} bytes", body.len());
+    if !status.is_success() {
+        let body = serde_json::from_slice(&body).ok();
+        return Err(status_error(status, &headers, body));
+    }
+    Ok(body)
+}
+/// converts an error of the middleware stack, which wraps reqwest errors,
+/// into a transport or timeout error
+fn transport_error(e: reqwest_middleware::Error) -> super::Error {
+    match e {
+        reqwest_middleware::Error::Reqwest(e) => reqwest_error(e),
+        e => super::Error::Transport {
+            reason: e.to_string(),
+        },
+    }
+}
+/// converts a reqwest error into a transport or timeout error
+fn reqwest_error(e: reqwest::Error) -> super::Error {
+    match e.is_timeout() {
+        true => super::Error::Timeout {
+            reason: e.to_string(),
+        },
+        false => super::Error::Transport {
+            reason: e.to_string(),
+        },
+    }
+}
+/// returns the error that matches the status of an unsuccessful server
+/// response. JSON:API error documents are decoded and attached to the error.
+///
+/// ## Parameters
+/// * `status` : status code of the response
+/// * `headers` : headers of the response, which tell how long to wait when
+///   the request was rate limited
+/// * `body` : response body, if it is JSON
+fn status_error(
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: Option<serde_json::Value>,
+) -> super::Error {
+    let failure = body
+        .as_ref()
+        .and_then(|v| serde_json::from_value::<Failure>(v.clone()).ok());
+    match status {
+        StatusCode::UNAUTHORIZED => super::Error::Unauthorized { failure },
+        StatusCode::NOT_FOUND => super::Error::NotFound { failure },
+        StatusCode::TOO_MANY_REQUESTS => super::Error::RateLimited {
+            retry_after: retry_after(headers),
+            failure,
+        },
+        _ => match failure {
+            Some(failure) => super::Error::Failure { status, failure },
+            None => super::Error::Status { status, body },
+        },
+    }
+}
+/// converts a server response into the expected type. The error tells the
+/// path of the value that could not be converted.
+fn from_value<R>(response: serde_json::Value) -> Result<R, super::Error>
+where
+    R: DeserializeOwned,
+{
+    serde_path_to_error::deserialize(response).map_err(|e| {
+        super::Error::Deserialize {
+            path: e.path().to_string(),
+            reason: e.inner().to_string().replace('\\', ""),
+        }
+    })
+}
+/// traces the payload of a request.
+///
+/// SECURITY: payloads are printed with `Debug` rather than serialized, so
+/// sensitive values, e.g `core::Secret`, are redacted. Payloads that hold
+/// sensitive values need to use types whose `Debug` redacts them.
+fn trace_payload<T: Debug>(payload: &T) {
+    tracing::trace!("\nRequest Payload Data:\n{:#?}", payload);
+}
+/// sets serialized payload as request body. `null` payloads, e.g `()`, are
+/// not sent as some endpoints reject requests that have a body.
+fn json_body<T>(
+    request: reqwest_middleware::RequestBuilder,
+    payload: &T,
+) -> reqwest_middleware::RequestBuilder
+where
+    T: Serialize,
+{
+    let body = serde_json::to_vec(payload).unwrap();
+    if body == b"null" {
+        return request;
+    }
+    request.body(reqwest::Body::from(body))
+}
+
 #[async_impl]
 impl super::HttpClient for Client {
     #[inline]
@@ -206,39 +465,16 @@ impl super::HttpClient for Client {
         let span: tracing::Span = tracing::span!(tracing::Level::INFO, "get");
         let _guard = span.enter();
         let response = self
-            .exec::<_, serde_json::Value>(
-                Method::GET,
-                url.as_ref(),
-                headers,
-                |req| {
-                    if let Some(payload) = payload {
-                        // TODO: confirm that this does not override
-                        // content-type header
-                        return req.query(payload);
-                    }
-                    req
-                },
-            )
-            .await
-            .map_err(|e| {
-                // tracing::error!("\nServer Response Error:\n{:?}", &e);
-                e
-            })?;
-        let response: R = serde_json::from_value(response).map_err(
-            |e: serde_json::Error| {
-                let e: String = e.to_string().replace("\\", "");
-
-                let e = super::Error::Response {
-                    canonical_reason: e,
-                    status: Some(
-                        reqwest::StatusCode::BAD_REQUEST.as_str().to_string(),
-                    ),
-                    body: None,
-                };
-                return e;
-            },
-        )?;
-        Ok(response)
+            .exec(Method::GET, url.as_ref(), headers, |req| {
+                if let Some(payload) = payload {
+                    // TODO: confirm that this does not override
+                    // content-type header
+                    return req.query(payload);
+                }
+                req
+            })
+            .await?;
+        from_value(response)
     }
 
     #[inline]
@@ -266,38 +502,14 @@ impl super::HttpClient for Client {
         // ─────────────────────────────────────────────────────────────────────────────
         let span: tracing::Span = tracing::span!(tracing::Level::INFO, "post");
         let _guard = span.enter();
-        tracing::trace!(
-            "\nRequest Payload Data:\n{}",
-            serde_json::to_string_pretty(&payload).unwrap()
-        );
+        trace_payload(&payload);
         let response = self
-            .exec::<_, serde_json::Value>(
-                Method::POST,
-                url.as_ref(),
-                headers,
-                |req| {
-                    req.body(reqwest::Body::from(
-                        serde_json::to_vec(&payload).unwrap(),
-                    ))
-                },
-            )
+            .exec(Method::POST, url.as_ref(), headers, |req| {
+                json_body(req, &payload)
+            })
             .await?;
 
-        let response: R = serde_json::from_value(response).map_err(
-            |e: serde_json::Error| {
-                let e: String = e.to_string().replace("\\", "");
-
-                let e = super::Error::Response {
-                    canonical_reason: e,
-                    status: Some(
-                        reqwest::StatusCode::BAD_REQUEST.as_str().to_string(),
-                    ),
-                    body: None,
-                };
-                return e;
-            },
-        )?;
-        Ok(response)
+        from_value(response)
     }
     #[inline]
     async fn put<R, S, T>(
@@ -313,38 +525,14 @@ impl super::HttpClient for Client {
     {
         let span: tracing::Span = tracing::span!(tracing::Level::INFO, "put");
         let _guard = span.enter();
-        tracing::trace!(
-            "\nRequest Payload Data:\n{}",
-            serde_json::to_string_pretty(&payload).unwrap()
-        );
+        trace_payload(&payload);
         let response = self
-            .exec::<_, serde_json::Value>(
-                Method::PUT,
-                url.as_ref(),
-                headers,
-                |req| {
-                    req.body(reqwest::Body::from(
-                        serde_json::to_vec(&payload).unwrap(),
-                    ))
-                },
-            )
+            .exec(Method::PUT, url.as_ref(), headers, |req| {
+                json_body(req, &payload)
+            })
             .await?;
 
-        let response: R = serde_json::from_value(response).map_err(
-            |e: serde_json::Error| {
-                let e: String = e.to_string().replace("\\", "");
-
-                let e = super::Error::Response {
-                    canonical_reason: e,
-                    status: Some(
-                        reqwest::StatusCode::BAD_REQUEST.as_str().to_string(),
-                    ),
-                    body: None,
-                };
-                return e;
-            },
-        )?;
-        Ok(response)
+        from_value(response)
     }
 
     #[inline]
@@ -362,40 +550,16 @@ impl super::HttpClient for Client {
         let span: tracing::Span =
             tracing::span!(tracing::Level::INFO, "patch");
         let _guard = span.enter();
-        tracing::trace!(
-            "\nRequest Payload Data:\n{}",
-            serde_json::to_string_pretty(&payload).unwrap()
-        );
+        trace_payload(&payload);
         let response = self
-            .exec::<_, serde_json::Value>(
-                Method::PATCH,
-                url.as_ref(),
-                headers,
-                |req| {
-                    // NOTE: using `response.json::<serde_json::Value>()` method to convert the response to JSON overrides
-                    // "content-type" to "application/json" which is undesirable thus we are
-                    // not using that method.
-                    req.body(reqwest::Body::from(
-                        // XXX: why does this work ?
-                        serde_json::to_vec(&payload).unwrap(),
-                    ))
-                },
-            )
+            .exec(Method::PATCH, url.as_ref(), headers, |req| {
+                // NOTE: using `response.json::<serde_json::Value>()` method to convert the response to JSON overrides
+                // "content-type" to "application/json" which is undesirable thus we are
+                // not using that method.
+                json_body(req, &payload)
+            })
             .await?;
-        let response: R = serde_json::from_value(response).map_err(
-            |e: serde_json::Error| {
-                let e: String = e.to_string().replace("\\", "");
-                let e = super::Error::Response {
-                    canonical_reason: e,
-                    status: Some(
-                        reqwest::StatusCode::BAD_REQUEST.as_str().to_string(),
-                    ),
-                    body: None,
-                };
-                return e;
-            },
-        )?;
-        Ok(response)
+        from_value(response)
     }
 
     #[inline]
@@ -411,31 +575,11 @@ impl super::HttpClient for Client {
         T: Serialize + Debug + Send + Sync,
     {
         let response = self
-            .exec::<_, serde_json::Value>(
-                Method::DELETE,
-                url.as_ref(),
-                headers,
-                |req| {
-                    req.body(reqwest::Body::from(
-                        serde_json::to_vec(&payload).unwrap(),
-                    ))
-                },
-            )
+            .exec(Method::DELETE, url.as_ref(), headers, |req| {
+                json_body(req, &payload)
+            })
             .await?;
-        let response: R = serde_json::from_value(response).map_err(
-            |e: serde_json::Error| {
-                let e: String = e.to_string().replace("\\", "");
-                let e = super::Error::Response {
-                    canonical_reason: e,
-                    status: Some(
-                        reqwest::StatusCode::BAD_REQUEST.as_str().to_string(),
-                    ),
-                    body: None,
-                };
-                return e;
-            },
-        )?;
-        Ok(response)
+        from_value(response)
     }
 }
 // ────────────────────────────────────────────────────────────
@@ -449,6 +593,15 @@ pub struct Builder {
     max_retry_interval: u64,
     /// Maximum number of allowed retries attempts.
     max_retries: u32,
+    /// Maximum number of requests sent per second. `0` disables client-side
+    /// rate limiting.
+    requests_per_second: u32,
+    /// Maximum waiting time before retrying a rate limited request in
+    /// **milliseconds**, which caps the time the server asks to wait.
+    max_retry_after: u64,
+    /// Maximum total waiting time of operations that poll the server, e.g
+    /// waiting for a run, in **milliseconds**.
+    wait_timeout: u64,
 }
 // ────────────────────────────────────────────────────────────
 impl Builder {
@@ -508,7 +661,51 @@ impl Builder {
     pub fn get_max_retries(&self) -> u32 {
         self.max_retries
     }
+    /// sets client requests_per_second. `0` disables client-side rate
+    /// limiting.
+    pub fn set_requests_per_second(
+        self,
+        arg: u32,
+    ) -> miette::Result<Self, Box<dyn std::error::Error>> {
+        let mut res = self.clone();
+        res.requests_per_second = arg;
+        Ok(res)
+    }
+    /// return client requests_per_second
+    pub fn get_requests_per_second(&self) -> u32 {
+        self.requests_per_second
+    }
+    /// sets client max_retry_after
+    pub fn set_max_retry_after(
+        self,
+        arg: u64,
+    ) -> miette::Result<Self, Box<dyn std::error::Error>> {
+        let mut res = self.clone();
+        res.max_retry_after = arg;
+        Ok(res)
+    }
+    /// return client max_retry_after
+    pub fn get_max_retry_after(&self) -> u64 {
+        self.max_retry_after
+    }
+    /// sets client wait_timeout
+    pub fn set_wait_timeout(
+        self,
+        arg: u64,
+    ) -> miette::Result<Self, Box<dyn std::error::Error>> {
+        let mut res = self.clone();
+        res.wait_timeout = arg;
+        Ok(res)
+    }
+    /// return client wait_timeout
+    pub fn get_wait_timeout(&self) -> u64 {
+        self.wait_timeout
+    }
     /// builds and returns upstream server client that supports request retries with exponential backoff that uses an exponent base of 2.
+    ///
+    /// Requests are throttled to `requests_per_second`, and rate limited
+    /// requests are retried after the time the server asks to wait in the
+    /// `Retry-After` or `X-RateLimit-Reset` header rather than backing off.
     pub fn build(
         self,
     ) -> miette::Result<super::ReqwestClient, Box<dyn std::error::Error>> {
@@ -532,10 +729,27 @@ impl Builder {
                 Duration::from_millis(self.max_retry_interval),
             )
             .build_with_max_retries(self.max_retries);
+        let rate_limiter = RateLimiter::new(
+            self.requests_per_second,
+            self.max_retries,
+            Duration::from_millis(self.max_retry_after),
+        );
+        // NOTE: the retry middleware fails on requests whose body is
+        // streamed, so they are sent by a client that only rate limits them
+        let streaming_client = ClientBuilder::new(client.clone())
+            .with(rate_limiter.clone())
+            .build();
+        // NOTE: the rate limiter is added last so that it throttles every
+        // attempt of the retry middleware
         let client = ClientBuilder::new(client)
-            .with(RetryTransientMiddleware::new_with_policy(retry_policy))
+            .with(RetryTransientMiddleware::new_with_policy_and_strategy(
+                retry_policy,
+                RetryStrategy,
+            ))
+            .with(rate_limiter.clone())
             .build();
-        Ok(Client::new(client))
+        Ok(Client::with_rate_limiter(client, rate_limiter)
+            .with_streaming_client(streaming_client))
     }
 }
 // ────────────────────────────────────────────────────────────
@@ -546,6 +760,9 @@ impl Default for Builder {
             min_retry_interval: DEFAULT_MIN_RETRY_INTERVAL,
             max_retry_interval: DEFAULT_MAX_RETRY_INTERVAL,
             max_retries: DEFAULT_MAX_RETRIES,
+            requests_per_second: DEFAULT_REQUESTS_PER_SECOND,
+            max_retry_after: DEFAULT_MAX_RETRY_AFTER.as_millis() as u64,
+            wait_timeout: DEFAULT_WAIT_TIMEOUT,
         }
     }
 }
@@ -555,7 +772,7 @@ mod tests {
 
     // cargo test --all-targets -- "core::reqwest::tests" --nocapture
     // cargo watch -cx 'test --all-targets -- "core::reqwest::tests" --nocapture'
-    use super::*;
+    use {super::*, reqwest::header::RETRY_AFTER};
     // write test cases for all setter methods
     #[test]
     fn set_timeout() {
@@ -591,6 +808,27 @@ mod tests {
         let builder = builder.set_max_retries(max_retries);
         assert_eq!(builder.unwrap().get_max_retries(), max_retries);
     }
+    #[test]
+    fn set_requests_per_second() {
+        let builder = Builder::new();
+        assert_eq!(builder.get_requests_per_second(), 30);
+        let builder = builder.set_requests_per_second(0);
+        assert_eq!(builder.unwrap().get_requests_per_second(), 0);
+    }
+    #[test]
+    fn set_max_retry_after() {
+        let builder = Builder::new();
+        assert_eq!(builder.get_max_retry_after(), 60 * 1000);
+        let builder = builder.set_max_retry_after(50);
+        assert_eq!(builder.unwrap().get_max_retry_after(), 50);
+    }
+    #[test]
+    fn set_wait_timeout() {
+        let builder = Builder::new();
+        assert_eq!(builder.get_wait_timeout(), 60 * 60 * 1000);
+        let builder = builder.set_wait_timeout(50);
+        assert_eq!(builder.unwrap().get_wait_timeout(), 50);
+    }
     #[tokio::test]
     async fn build() {
         let builder = Builder::new();
@@ -627,18 +865,70 @@ mod tests {
             client.get::<serde_json::Value, &str>(url, None, None).await;
         assert!(result.is_err());
         let actual = result.unwrap_err();
-        let expected: crate::core::Error = crate::core::Error::Response {
-            canonical_reason: "Unauthorized".to_string(),
-            status: Some("401".to_string()),
-            body: Some(serde_json::json!({
+        let expected: crate::core::Error = crate::core::Error::Unauthorized {
+            failure: Some(crate::jsonapi::Failure::from(serde_json::json!({
                 "errors": [{
                 "status": "401",
                 "title":"unauthorized" ,
                 }]
-            })),
+            }))),
         };
         assert_eq!(actual, expected);
     }
+    #[test]
+    fn test_status_error() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("2"));
+        let actual =
+            status_error(StatusCode::TOO_MANY_REQUESTS, &headers, None);
+        assert_eq!(actual.retry_after(), Some(Duration::from_secs(2)));
+        let mut headers = HeaderMap::new();
+        headers.insert("x-ratelimit-reset", HeaderValue::from_static("0.5"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_millis(500)));
+        let body = serde_json::json!({
+            "errors": [{
+                "status": "422",
+                "title": "invalid attribute",
+                "detail": "Name has already been taken",
+                "source": { "pointer": "/data/attributes/name" }
+            }]
+        });
+        let actual = status_error(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            &HeaderMap::new(),
+            Some(body),
+        );
+        let failure = actual.failure().unwrap();
+        assert_eq!(failure.errors_for_attribute("name").len(), 1);
+        assert_eq!(
+            crate::jsonapi::Failure::try_from(actual).map(|v| v.to_string()),
+            Ok("Failure: [Error(422): invalid attribute. Name has already been taken.]".to_string())
+        );
+        let actual =
+            status_error(StatusCode::BAD_GATEWAY, &HeaderMap::new(), None);
+        assert_eq!(actual.status(), Some(StatusCode::BAD_GATEWAY));
+        assert!(!actual.is_not_found());
+    }
+    #[test]
+    fn test_from_value() {
+        #[derive(Debug, serde::Deserialize, serde::Serialize)]
+        struct Attributes {
+            count: u32,
+        }
+        let input = serde_json::json!({ "data": [{ "count": "one" }] });
+        let actual =
+            from_value::<crate::jsonapi::Failure>(serde_json::json!({}));
+        assert!(actual.unwrap_err().is_deserialize());
+        let actual = from_value::<
+            std::collections::HashMap<String, Vec<Attributes>>,
+        >(input);
+        match actual.unwrap_err() {
+            crate::core::Error::Deserialize { path, .. } => {
+                assert_eq!(path, "data[0].count")
+            }
+            e => panic!("unexpected error: {e:?}"),
+        }
+    }
     /// enables tracing in tests. used for debugging
     fn tracing() {
         // NOTE: set tracing level with env vars; e.g
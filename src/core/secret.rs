@@ -0,0 +1,55 @@
+use {
+    serde::{Deserialize, Serialize},
+    zeroize::Zeroizing,
+};
+/// This struct holds a sensitive value, e.g a sensitive output or variable.
+///
+/// The value is wiped from memory when it is dropped, and it is never
+/// printed by `Debug`. It is serialized as a plain string, so it is sent to
+/// and read from the API unchanged.
+#[derive(PartialEq, Eq, Clone, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Secret(Zeroizing<String>);
+impl Secret {
+    /// returns a new secret that holds the value
+    pub fn new<S: Into<String>>(value: S) -> Self {
+        Secret(Zeroizing::new(value.into()))
+    }
+    /// returns the sensitive value
+    pub fn expose(&self) -> &str {
+        self.0.as_str()
+    }
+}
+impl std::fmt::Debug for Secret {
+    /// SECURITY: the value is never printed
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+impl From<String> for Secret {
+    fn from(arg: String) -> Self {
+        Secret::new(arg)
+    }
+}
+impl From<&str> for Secret {
+    fn from(arg: &str) -> Self {
+        Secret::new(arg)
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "core::secret::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "core::secret::tests" --nocapture'
+    use super::*;
+    #[test]
+    fn test_secret() -> Result<(), serde_json::Error> {
+        let secret: Secret = serde_json::from_str(r#""hunter2""#)?;
+        assert_eq!(secret.expose(), "hunter2");
+        assert_eq!(format!("{secret:?}"), "Secret(***)");
+        assert_eq!(serde_json::to_string(&secret)?, r#""hunter2""#);
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
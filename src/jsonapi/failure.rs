@@ -73,23 +73,36 @@ pub struct Error {
     source: Option<Source>,
 }
 // ────────────────────────────────────────────────────────────
+impl Error {
+    /// returns JSON pointer to the value of the request document that
+    /// caused the error, e.g `/data/attributes/name`
+    pub fn pointer(&self) -> Option<&str> {
+        self.source.as_ref()?.pointer.as_deref()
+    }
+    /// returns name of the request attribute that caused the error, e.g
+    /// `name` for the `/data/attributes/name` pointer. Names are kebab-case,
+    /// like the attributes of request documents.
+    pub fn attribute(&self) -> Option<&str> {
+        self.pointer()?.strip_prefix("/data/attributes/")
+    }
+}
 impl Display for Error {
     /// helps with pretty printing the error as string
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let status = self.get_status();
         let title = self.get_title().trim();
         let title = title.trim_start_matches('.').trim_end_matches('.');
-        write!(f, "Error({})", status)?;
-        if title.len() > 0 || self.get_detail().is_some() {
+        write!(f, "Error({status})")?;
+        if !title.is_empty() || self.get_detail().is_some() {
             write!(f, ":")?;
         }
-        if title.len() > 0 {
-            write!(f, " {}.", title)?;
+        if !title.is_empty() {
+            write!(f, " {title}.")?;
         }
         if self.get_detail().is_some() {
             let detail: &String = self.get_detail().as_ref().unwrap();
             let detail = detail.trim_start_matches('.').trim_end_matches('.');
-            write!(f, " {}.", detail)?;
+            write!(f, " {detail}.")?;
         }
         Ok(())
     }
@@ -100,6 +113,9 @@ impl Display for Error {
 ///
 /// It seems like the response is always an array with
 /// a single JSON API error object.
+///
+/// The client decodes it from the body of every error response, so it is
+/// available through `core::Error::failure`.
 #[derive(
     PartialEq, Clone, Default, Deserialize, Serialize, Getters, Debug,
 )]
@@ -108,15 +124,32 @@ pub struct Failure {
     errors: Vec<Error>,
 }
 // ────────────────────────────────────────────────────────────
+impl Failure {
+    /// returns errors whose source is the given JSON pointer
+    pub fn errors_at(&self, pointer: &str) -> Vec<&Error> {
+        self.errors
+            .iter()
+            .filter(|v| v.pointer() == Some(pointer))
+            .collect()
+    }
+    /// returns errors caused by the given request attribute, e.g
+    /// `auto-apply`
+    pub fn errors_for_attribute(&self, attribute: &str) -> Vec<&Error> {
+        self.errors
+            .iter()
+            .filter(|v| v.attribute() == Some(attribute))
+            .collect()
+    }
+}
 impl Display for Failure {
     /// helps with pretty printing the server response as string
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.get_errors().len() == 0 {
+        if self.get_errors().is_empty() {
             return write!(f, "No errors.");
         }
         write!(f, "Failure: [")?;
-        for (idx, elem) in (&self.errors).into_iter().enumerate() {
-            write!(f, "{}", elem)?;
+        for (idx, elem) in self.errors.iter().enumerate() {
+            write!(f, "{elem}")?;
             if idx != self.errors.len() - 1 {
                 write!(f, ", ")?;
             }
@@ -139,61 +172,25 @@ impl From<Failure> for serde_json::Value {
 }
 
 impl TryFrom<crate::core::Error> for Failure {
-    type Error = Self;
-    /// Convert a `crate::core::Error` into a `Failure` object
+    type Error = crate::core::Error;
+    /// returns the JSON:API error document the server responded with. The
+    /// error is given back if the server did not respond with one, e.g when
+    /// the request was not sent.
     fn try_from(value: crate::core::Error) -> Result<Self, Self::Error> {
-        let span = tracing::span!(tracing::Level::INFO, "Failure");
-        let _guard = span.enter();
-
-        let span = tracing::span!(tracing::Level::INFO, "try_from");
-        let _guard = span.enter();
-
-        // NOTE: deconstruct value into `crate::core::Error::Response` and extract the
-        // fields
-        let (canonical_reason, status, body) = match value {
-            crate::core::Error::Response {
-                canonical_reason,
-                status,
-                body,
-            } => (canonical_reason, status, body),
-        };
-
-        if body.is_some() {
-            let span: tracing::Span =
-                tracing::span!(tracing::Level::INFO, "JSON Conversion");
-
-            let _guard = span.enter();
-            tracing::trace!("\nunwrapping body");
-
-            let body = body.unwrap();
-            return Err(serde_json::from_value::<Failure>(body).map_err(
-                |e: serde_json::Error| {
-                    let e = Failure {
-                        errors: vec![Error {
-                            status: "400".to_string(),
-                            title: e.to_string(),
-                            ..Default::default()
-                        }],
-                    };
-                    // tracing::error!("\n{:?}", &e);
-                    return e;
-                },
-            )?);
+        match value {
+            crate::core::Error::Failure { failure, .. }
+            | crate::core::Error::Unauthorized {
+                failure: Some(failure),
+            }
+            | crate::core::Error::NotFound {
+                failure: Some(failure),
+            }
+            | crate::core::Error::RateLimited {
+                failure: Some(failure),
+                ..
+            } => Ok(failure),
+            value => Err(value),
         }
-
-        // NOTE: do not put a tracing::error!(...) for this error as it can lead
-        // to duplicate log entries
-        let e = Failure {
-            errors: vec![Error {
-                status: status.unwrap_or("400".to_string()),
-                title: canonical_reason,
-                ..Default::default()
-            }],
-        };
-        let msg: String = serde_json::to_string_pretty(&e).unwrap();
-        let msg: String = msg.replace("\\\"", "\"");
-        tracing::error!("\nforming failure response array:\n{}", msg);
-        return Err(e);
     }
 }
 
@@ -288,6 +285,38 @@ mod tests {
         let actual = input.to_string();
         assert_eq!(expected, actual);
     }
+    #[test]
+    fn test_failure_errors_for_attribute() {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs#error-objects
+        let input: crate::jsonapi::Failure = serde_json::json!({
+            "errors": [
+                {
+                    "status": "422",
+                    "title": "invalid attribute",
+                    "source": { "pointer": "/data/attributes/name" },
+                    "detail": "Name has already been taken"
+                },
+                {
+                    "status": "422",
+                    "title": "invalid attribute",
+                    "source": { "pointer": "/data/relationships/project" },
+                    "detail": "Project must exist"
+                }
+            ]
+        })
+        .into();
+        let actual = input.errors_for_attribute("name");
+        assert_eq!(actual.len(), 1);
+        assert_eq!(
+            actual[0].get_detail().as_deref(),
+            Some("Name has already been taken")
+        );
+        let actual = input.errors_at("/data/relationships/project");
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].attribute(), None);
+        assert!(input.errors_for_attribute("description").is_empty());
+    }
     // ────────────────────────────────────────────────────────────
     #[test]
     fn test_failure_with_no_errors() {
@@ -0,0 +1,255 @@
+use {
+    super::{
+        relationship::{Many, One, Relationship, ResourceIdentifier},
+        success::Data,
+    },
+    serde::{de::DeserializeOwned, Deserialize, Serialize},
+    serde_json::Value,
+    std::collections::HashMap,
+};
+// ────────────────────────────────────────────────────────────
+/// This struct holds the resources of the `included` member of a compound
+/// document.
+///
+/// Included resources usually have other types than the primary data, e.g
+/// `?include=current_run,organization` on a workspace returns runs and
+/// organizations. They are kept as raw JSON and indexed by `(type, id)`, so
+/// that they are converted into a concrete resource type only when they are
+/// looked up.
+#[derive(PartialEq, Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(from = "Vec<Value>", into = "Vec<Value>")]
+pub struct Included {
+    /// resources in the order of the response
+    resources: Vec<Value>,
+    /// position of each resource in `resources`, keyed by `(type, id)`
+    index: HashMap<(String, String), usize>,
+}
+impl Included {
+    /// returns the number of included resources
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+    /// returns true if there are no included resources
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+    /// returns an iterator over the raw included resources
+    pub fn iter(&self) -> std::slice::Iter<'_, Value> {
+        self.resources.iter()
+    }
+    /// adds a raw resource. A resource with the same type and ID is
+    /// replaced.
+    pub fn insert_raw(&mut self, resource: Value) {
+        match key(&resource) {
+            Some(key) => match self.index.get(&key) {
+                Some(position) => self.resources[*position] = resource,
+                None => {
+                    self.index.insert(key, self.resources.len());
+                    self.resources.push(resource);
+                }
+            },
+            // NOTE: resources without type or ID cannot be looked up, but
+            // are kept so that the document serializes back as it was read.
+            None => self.resources.push(resource),
+        }
+    }
+    /// adds a typed resource. A resource with the same type and ID is
+    /// replaced.
+    pub fn insert<ATTRIBUTES, RELATIONSHIPS>(
+        &mut self,
+        resource: &Data<ATTRIBUTES, RELATIONSHIPS>,
+    ) -> Result<(), serde_json::Error>
+    where
+        ATTRIBUTES: Serialize,
+        RELATIONSHIPS: Serialize,
+    {
+        self.insert_raw(serde_json::to_value(resource)?);
+        Ok(())
+    }
+    /// returns the raw resource with the given type and ID
+    pub fn get_raw(&self, type_: &str, id: &str) -> Option<&Value> {
+        self.index
+            .get(&(type_.to_string(), id.to_string()))
+            .map(|position| &self.resources[*position])
+    }
+    /// returns the resource with the given type and ID, converted into the
+    /// given resource type
+    pub fn get<ATTRIBUTES, RELATIONSHIPS>(
+        &self,
+        type_: &str,
+        id: &str,
+    ) -> Result<Option<Data<ATTRIBUTES, RELATIONSHIPS>>, serde_json::Error>
+    where
+        ATTRIBUTES: DeserializeOwned,
+        RELATIONSHIPS: DeserializeOwned,
+    {
+        self.get_raw(type_, id)
+            .map(|v| serde_json::from_value(v.clone()))
+            .transpose()
+    }
+    /// returns the resource the given identifier points to, converted into
+    /// the given resource type
+    pub fn get_identified<ATTRIBUTES, RELATIONSHIPS>(
+        &self,
+        identifier: &ResourceIdentifier,
+    ) -> Result<Option<Data<ATTRIBUTES, RELATIONSHIPS>>, serde_json::Error>
+    where
+        ATTRIBUTES: DeserializeOwned,
+        RELATIONSHIPS: DeserializeOwned,
+    {
+        self.get(&identifier._type, &identifier.id)
+    }
+    /// returns the included resource a to-one relationship points to.
+    /// Returns `None` when the linkage is `null` or missing, or the resource
+    /// was not included.
+    pub fn resolve<ATTRIBUTES, RELATIONSHIPS>(
+        &self,
+        relationship: &Relationship<One>,
+    ) -> Result<Option<Data<ATTRIBUTES, RELATIONSHIPS>>, serde_json::Error>
+    where
+        ATTRIBUTES: DeserializeOwned,
+        RELATIONSHIPS: DeserializeOwned,
+    {
+        match relationship.identifier() {
+            Some(identifier) => self.get_identified(identifier),
+            None => Ok(None),
+        }
+    }
+    /// returns the included resources a to-many relationship points to.
+    /// Resources that were not included are skipped.
+    pub fn resolve_many<ATTRIBUTES, RELATIONSHIPS>(
+        &self,
+        relationship: &Relationship<Many>,
+    ) -> Result<Vec<Data<ATTRIBUTES, RELATIONSHIPS>>, serde_json::Error>
+    where
+        ATTRIBUTES: DeserializeOwned,
+        RELATIONSHIPS: DeserializeOwned,
+    {
+        let mut res = Vec::new();
+        for identifier in relationship.identifiers() {
+            if let Some(resource) = self.get_identified(identifier)? {
+                res.push(resource);
+            }
+        }
+        Ok(res)
+    }
+}
+/// returns the `(type, id)` key of a resource
+fn key(resource: &Value) -> Option<(String, String)> {
+    let type_ = resource.get("type")?.as_str()?;
+    let id = resource.get("id")?.as_str()?;
+    Some((type_.to_string(), id.to_string()))
+}
+impl From<Vec<Value>> for Included {
+    fn from(resources: Vec<Value>) -> Self {
+        let mut res = Self::default();
+        for resource in resources {
+            res.insert_raw(resource);
+        }
+        res
+    }
+}
+impl From<Included> for Vec<Value> {
+    fn from(included: Included) -> Self {
+        included.resources
+    }
+}
+impl<'a> IntoIterator for &'a Included {
+    type Item = &'a Value;
+    type IntoIter = std::slice::Iter<'a, Value>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "jsonapi::included::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "jsonapi::included::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[derive(PartialEq, Debug, Default, Deserialize, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct RunAttributes {
+        status: String,
+    }
+    #[derive(PartialEq, Debug, Default, Deserialize, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct OrganizationAttributes {
+        email: String,
+    }
+    #[derive(PartialEq, Debug, Default, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct WorkspaceRelationships {
+        current_run: Relationship<One>,
+        organization: Relationship<One>,
+        latest_run: Relationship<One>,
+    }
+    #[test]
+    fn test_resolve() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs#inclusion-of-related-resources
+        let included: Included = serde_json::from_value(json!([
+            {
+                "id": "run-gfoHFBZ6p5KBHAdo",
+                "type": "runs",
+                "attributes": { "status": "planned" }
+            },
+            {
+                "id": "hashicorp",
+                "type": "organizations",
+                "attributes": { "email": "admin@example.com" }
+            }
+        ]))?;
+        let relationships: WorkspaceRelationships =
+            serde_json::from_value(json!({
+                "current-run": {
+                    "data": { "id": "run-gfoHFBZ6p5KBHAdo", "type": "runs" }
+                },
+                "organization": {
+                    "data": { "id": "hashicorp", "type": "organizations" }
+                },
+                "latest-run": { "data": null },
+            }))?;
+        let run: Data<RunAttributes, Value> =
+            included.resolve(&relationships.current_run)?.unwrap();
+        assert_eq!(run.attributes.unwrap().status, "planned");
+        let organization: Data<OrganizationAttributes, Value> =
+            included.resolve(&relationships.organization)?.unwrap();
+        assert_eq!(
+            organization.attributes.unwrap().email,
+            "admin@example.com"
+        );
+        let latest: Option<Data<RunAttributes, Value>> =
+            included.resolve(&relationships.latest_run)?;
+        assert_eq!(latest, None);
+        Ok(())
+    }
+    #[test]
+    fn test_resolve_many() -> Result<(), serde_json::Error> {
+        let mut included = Included::default();
+        for (id, status) in [("run-1", "applied"), ("run-2", "errored")] {
+            included.insert(&Data::<RunAttributes, Value> {
+                _type: "runs".to_string(),
+                id: Some(id.to_string()),
+                attributes: Some(RunAttributes {
+                    status: status.to_string(),
+                }),
+                ..Default::default()
+            })?;
+        }
+        let relationship =
+            Relationship::many("runs", ["run-2", "run-3", "run-1"]);
+        let runs: Vec<Data<RunAttributes, Value>> =
+            included.resolve_many(&relationship)?;
+        let ids: Vec<_> =
+            runs.iter().filter_map(|v| v.id.as_deref()).collect();
+        assert_eq!(ids, ["run-2", "run-1"]);
+        assert_eq!(
+            serde_json::to_value(&included)?.as_array().unwrap().len(),
+            2
+        );
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
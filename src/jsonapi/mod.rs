@@ -4,6 +4,13 @@
 
 /// This module contains objects that make up a failed JSON:API response.
 pub mod failure;
+/// This module contains the store of resources included in a compound
+/// document.
+pub mod included;
+/// This module contains relationship objects and resource identifiers.
+pub mod relationship;
+/// This module contains objects that make up a request document.
+pub mod request;
 /// This module contains objects that make up a successful JSON:API response.
 pub mod success;
 // ────────────────────────────────────────────────────────────
@@ -16,6 +23,19 @@ pub type Data<ATTRIBUTES, RELATIONSHIPS> =
     success::Data<ATTRIBUTES, RELATIONSHIPS>;
 pub type Success<ATTRIBUTES, RELATIONSHIPS> =
     success::Success<ATTRIBUTES, RELATIONSHIPS>;
+pub type SuccessList<ATTRIBUTES, RELATIONSHIPS> =
+    success::SuccessList<ATTRIBUTES, RELATIONSHIPS>;
+pub type Included = included::Included;
+// ────────────────────────────────────────────────────────────
+pub type One = relationship::One;
+pub type Many = relationship::Many;
+pub type ResourceIdentifier = relationship::ResourceIdentifier;
+pub type Relationship<LINKAGE> = relationship::Relationship<LINKAGE>;
+// ────────────────────────────────────────────────────────────
+pub type Request<ATTRIBUTES, RELATIONSHIPS = request::Relationships> =
+    request::Request<ATTRIBUTES, RELATIONSHIPS>;
+pub type RequestBuilder<ATTRIBUTES> = request::RequestBuilder<ATTRIBUTES>;
+pub type AnyRelationship = request::AnyRelationship;
 // ────────────────────────────────────────────────────────────
 pub type ErrorLinks = failure::Links;
 pub type ErrorSource = failure::Source;
@@ -0,0 +1,224 @@
+use {
+    super::success::Links,
+    getset::Getters,
+    serde::{Deserialize, Deserializer, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This type represents resource linkage of a to-one relationship, which is
+/// `None` when the linkage is `null`.
+pub type One = Option<ResourceIdentifier>;
+/// This type represents resource linkage of a to-many relationship.
+pub type Many = Vec<ResourceIdentifier>;
+// ────────────────────────────────────────────────────────────
+/// This struct represents a resource identifier object, which identifies a
+/// resource by its type and ID.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+pub struct ResourceIdentifier {
+    #[serde(rename = "type")]
+    pub _type: String,
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Value>,
+}
+impl ResourceIdentifier {
+    /// returns an identifier of the resource with the given type and ID
+    pub fn new(type_: &str, id: &str) -> Self {
+        Self {
+            _type: type_.to_string(),
+            id: id.to_string(),
+            meta: None,
+        }
+    }
+}
+/// This struct represents a relationship object. `LINKAGE` is either `One`
+/// or `Many`.
+///
+/// `data` is `None` when the relationship has no resource linkage, e.g the
+/// API only describes relationships that are not included by their `links`,
+/// which is different from a to-one relationship whose linkage is `null`,
+/// i.e `Some(None)`. Relationships without linkage are serialized without
+/// `data`, so sending them back leaves the relationship unchanged rather
+/// than clearing it.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+pub struct Relationship<LINKAGE> {
+    #[serde(
+        default,
+        deserialize_with = "present",
+        skip_serializing_if = "Option::is_none",
+        bound(deserialize = "LINKAGE: Deserialize<'de>")
+    )]
+    pub data: Option<LINKAGE>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub links: Option<Links>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta: Option<serde_json::Value>,
+}
+/// deserializes a value that is present, even if it is `null`, so that
+/// missing values, which are `None` by default, can be told apart
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+impl<LINKAGE> Relationship<LINKAGE> {
+    /// returns true if the relationship has resource linkage, even if it is
+    /// `null`
+    pub fn has_linkage(&self) -> bool {
+        self.data.is_some()
+    }
+}
+impl Relationship<One> {
+    /// returns a to-one relationship that references the given resource
+    pub fn one(type_: &str, id: &str) -> Self {
+        Self::from(ResourceIdentifier::new(type_, id))
+    }
+    /// returns a to-one relationship with `null` linkage, which clears the
+    /// relationship when it is sent
+    pub fn null() -> Self {
+        Self {
+            data: Some(None),
+            ..Default::default()
+        }
+    }
+    /// returns the identifier of the referenced resource, if any
+    pub fn identifier(&self) -> Option<&ResourceIdentifier> {
+        self.data.as_ref().and_then(Option::as_ref)
+    }
+    /// returns ID of the referenced resource, if any
+    pub fn id(&self) -> Option<&str> {
+        self.identifier().map(|v| v.id.as_str())
+    }
+}
+impl From<ResourceIdentifier> for Relationship<One> {
+    /// returns a to-one relationship that references the identified resource
+    fn from(arg: ResourceIdentifier) -> Self {
+        Self {
+            data: Some(Some(arg)),
+            ..Default::default()
+        }
+    }
+}
+impl From<Many> for Relationship<Many> {
+    /// returns a to-many relationship that references the identified
+    /// resources
+    fn from(arg: Many) -> Self {
+        Self {
+            data: Some(arg),
+            ..Default::default()
+        }
+    }
+}
+impl Relationship<Many> {
+    /// returns a to-many relationship that references the given resources
+    pub fn many<I, S>(type_: &str, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        ids.into_iter()
+            .map(|id| ResourceIdentifier::new(type_, id.as_ref()))
+            .collect::<Many>()
+            .into()
+    }
+    /// returns identifiers of the referenced resources, which are empty
+    /// when the relationship has no linkage
+    pub fn identifiers(&self) -> &[ResourceIdentifier] {
+        self.data.as_deref().unwrap_or_default()
+    }
+    /// returns IDs of the referenced resources
+    pub fn ids(&self) -> Vec<&str> {
+        self.identifiers().iter().map(|v| v.id.as_str()).collect()
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "jsonapi::relationship::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "jsonapi::relationship::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[derive(PartialEq, Debug, Default, Deserialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct TestRelationships {
+        organization: Option<Relationship<One>>,
+        latest_run: Option<Relationship<One>>,
+        remote_state_consumers: Option<Relationship<Many>>,
+        outputs: Option<Relationship<Many>>,
+    }
+    #[test]
+    fn test_relationship_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/workspaces
+        let input = json!({
+            "organization": {
+                "data": { "id": "my-organization", "type": "organizations" }
+            },
+            "latest-run": { "data": null },
+            "remote-state-consumers": {
+                "links": {
+                    "related": "/api/v2/workspaces/ws-6jrRyVDv1J8zQMB5/relationships/remote-state-consumers"
+                }
+            },
+            "outputs": {
+                "data": [
+                    { "id": "wsout-V22qbeM92xb5mw9n", "type": "workspace-outputs" }
+                ]
+            }
+        });
+        let actual: TestRelationships = serde_json::from_value(input)?;
+        let organization = actual.organization.unwrap();
+        assert_eq!(organization.id(), Some("my-organization"));
+        assert_eq!(organization.identifier().unwrap()._type, "organizations");
+        let latest_run = actual.latest_run.unwrap();
+        assert_eq!(latest_run.id(), None);
+        assert!(latest_run.has_linkage());
+        let consumers = actual.remote_state_consumers.unwrap();
+        assert!(consumers.ids().is_empty());
+        assert!(!consumers.has_linkage());
+        assert!(consumers.links.as_ref().unwrap().get_related().is_some());
+        // NOTE: relationships without linkage are sent back without `data`,
+        // which does not clear them
+        let serialized = serde_json::to_value(&consumers)?;
+        assert!(serialized.get("data").is_none());
+        assert!(serialized.get("links").is_some());
+        assert_eq!(
+            actual.outputs.unwrap().ids(),
+            vec!["wsout-V22qbeM92xb5mw9n"]
+        );
+        Ok(())
+    }
+    #[test]
+    fn test_relationship_serialize() -> Result<(), serde_json::Error> {
+        assert_eq!(
+            serde_json::to_value(Relationship::one("projects", "prj-123"))?,
+            json!({ "data": { "type": "projects", "id": "prj-123" } })
+        );
+        assert_eq!(
+            serde_json::to_value(Relationship::null())?,
+            json!({ "data": null })
+        );
+        assert_eq!(
+            serde_json::to_value(Relationship::<One>::default())?,
+            json!({})
+        );
+        assert_eq!(
+            serde_json::to_value(Relationship::many("users", ["a", "b"]))?,
+            json!({
+                "data": [
+                    { "type": "users", "id": "a" },
+                    { "type": "users", "id": "b" }
+                ]
+            })
+        );
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,265 @@
+use {
+    super::{
+        relationship::{Many, One, Relationship},
+        success::Data,
+    },
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeMap,
+};
+// ────────────────────────────────────────────────────────────
+/// This type represents relationships of a request document, keyed by
+/// relationship name.
+pub type Relationships = BTreeMap<String, AnyRelationship>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents a relationship of a request document, which is
+/// either to-one or to-many.
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum AnyRelationship {
+    // NOTE: to-many relationships come first as `[]` would otherwise be
+    // read as a to-one relationship
+    Many(Relationship<Many>),
+    One(Relationship<One>),
+}
+impl From<Relationship<One>> for AnyRelationship {
+    fn from(arg: Relationship<One>) -> Self {
+        AnyRelationship::One(arg)
+    }
+}
+impl From<Relationship<Many>> for AnyRelationship {
+    fn from(arg: Relationship<Many>) -> Self {
+        AnyRelationship::Many(arg)
+    }
+}
+// ────────────────────────────────────────────────────────────
+/// This struct represents a JSON:API request document, i.e the payload of
+/// create and update requests.
+///
+/// Attributes are serialized as they are, so attribute types are expected to
+/// rename their fields with `#[serde(rename_all = "kebab-case")]`.
+///
+/// NOTE: unlike documents built with `serde_json::json!`, it keeps the
+/// attributes typed, so `Secret` values are not revealed by the request
+/// trace.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+pub struct Request<ATTRIBUTES, RELATIONSHIPS = Relationships> {
+    data: Data<ATTRIBUTES, RELATIONSHIPS>,
+}
+impl<ATTRIBUTES, RELATIONSHIPS> Request<ATTRIBUTES, RELATIONSHIPS> {
+    /// returns a request document with the given primary data
+    pub fn new(data: Data<ATTRIBUTES, RELATIONSHIPS>) -> Self {
+        Self { data }
+    }
+    /// consumes the request and returns its primary data
+    pub fn into_data(self) -> Data<ATTRIBUTES, RELATIONSHIPS> {
+        self.data
+    }
+}
+impl<ATTRIBUTES> Request<ATTRIBUTES> {
+    /// returns a builder of a request document for a resource of the given
+    /// type
+    pub fn builder(type_: &str) -> RequestBuilder<ATTRIBUTES> {
+        RequestBuilder::new(type_)
+    }
+}
+/// This struct builds a request document whose relationships are given by
+/// name.
+///
+/// # Examples
+///
+/// Basic usage:
+///
+/// ```
+/// use {serde_json::json, tfe_oxide::jsonapi::Request};
+/// let request = Request::builder("workspaces")
+///     .attributes(json!({ "name": "payments" }))
+///     .to_one("project", "projects", Some("prj-WsVcWRr7SSxRqvGA"))
+///     .build();
+/// assert_eq!(
+///     serde_json::to_value(request).unwrap(),
+///     json!({
+///         "data": {
+///             "type": "workspaces",
+///             "attributes": { "name": "payments" },
+///             "relationships": {
+///                 "project": {
+///                     "data": {
+///                         "type": "projects",
+///                         "id": "prj-WsVcWRr7SSxRqvGA"
+///                     }
+///                 }
+///             }
+///         }
+///     })
+/// );
+/// ```
+#[derive(PartialEq, Debug, Clone)]
+pub struct RequestBuilder<ATTRIBUTES> {
+    type_: String,
+    id: Option<String>,
+    attributes: Option<ATTRIBUTES>,
+    relationships: Relationships,
+}
+impl<ATTRIBUTES> RequestBuilder<ATTRIBUTES> {
+    /// returns a builder of a request document for a resource of the given
+    /// type
+    pub fn new(type_: &str) -> Self {
+        Self {
+            type_: type_.to_string(),
+            id: None,
+            attributes: None,
+            relationships: Relationships::new(),
+        }
+    }
+    /// sets ID of the resource, which update requests of some endpoints
+    /// require
+    pub fn id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+    /// sets attributes of the resource
+    pub fn attributes(mut self, attributes: ATTRIBUTES) -> Self {
+        self.attributes = Some(attributes);
+        self
+    }
+    /// sets a to-one relationship. The linkage is `null` when `id` is
+    /// `None`, which clears the relationship.
+    pub fn to_one(self, name: &str, type_: &str, id: Option<&str>) -> Self {
+        let relationship = match id {
+            Some(id) => Relationship::one(type_, id),
+            None => Relationship::null(),
+        };
+        self.relationship(name, relationship)
+    }
+    /// sets a to-many relationship
+    pub fn to_many<I, S>(self, name: &str, type_: &str, ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.relationship(name, Relationship::many(type_, ids))
+    }
+    /// sets a relationship, e.g one whose resource identifiers have `meta`
+    pub fn relationship<R>(mut self, name: &str, relationship: R) -> Self
+    where
+        R: Into<AnyRelationship>,
+    {
+        self.relationships
+            .insert(name.to_string(), relationship.into());
+        self
+    }
+    /// returns the request document
+    pub fn build(self) -> Request<ATTRIBUTES> {
+        Request::new(Data {
+            _type: self.type_,
+            id: self.id,
+            attributes: self.attributes,
+            links: None,
+            relationships: if self.relationships.is_empty() {
+                None
+            } else {
+                Some(self.relationships)
+            },
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "jsonapi::request::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "jsonapi::request::tests" --nocapture'
+    use {
+        super::*, crate::jsonapi::relationship::ResourceIdentifier,
+        serde_json::json,
+    };
+    #[derive(PartialEq, Debug, Default, Serialize)]
+    #[serde(rename_all = "kebab-case")]
+    struct TestAttributes {
+        name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        auto_apply: Option<bool>,
+    }
+    #[test]
+    fn test_request_serialize() -> Result<(), serde_json::Error> {
+        let attributes = TestAttributes {
+            name: "payments".to_string(),
+            auto_apply: Some(true),
+        };
+        let request = Request::builder("workspaces")
+            .id("ws-4j8p6jX1w33MiDC7")
+            .attributes(&attributes)
+            .to_one("project", "projects", None)
+            .to_many("tags", "tags", ["tag-1", "tag-2"])
+            .build();
+        let expected = json!({
+            "data": {
+                "type": "workspaces",
+                "id": "ws-4j8p6jX1w33MiDC7",
+                "attributes": { "name": "payments", "auto-apply": true },
+                "relationships": {
+                    "project": { "data": null },
+                    "tags": {
+                        "data": [
+                            { "type": "tags", "id": "tag-1" },
+                            { "type": "tags", "id": "tag-2" }
+                        ]
+                    }
+                }
+            }
+        });
+        assert_eq!(serde_json::to_value(&request)?, expected);
+        // NOTE: request documents are read back with typed relationships
+        let actual: Request<serde_json::Value> =
+            serde_json::from_value(expected)?;
+        let relationships = actual.get_data().relationships.as_ref().unwrap();
+        assert_eq!(
+            relationships["project"],
+            AnyRelationship::One(Relationship::null())
+        );
+        assert_eq!(
+            relationships["tags"],
+            AnyRelationship::Many(Relationship::many(
+                "tags",
+                ["tag-1", "tag-2"]
+            ))
+        );
+        let request = Request::<()>::builder("workspaces")
+            .to_many("tags", "tags", Vec::<String>::new())
+            .relationship(
+                "project",
+                Relationship::from(ResourceIdentifier {
+                    meta: Some(json!({ "default": true })),
+                    ..ResourceIdentifier::new("projects", "prj-123")
+                }),
+            )
+            .build();
+        assert_eq!(
+            serde_json::to_value(&request)?["data"]["relationships"],
+            json!({
+                "tags": { "data": [] },
+                "project": {
+                    "data": {
+                        "type": "projects",
+                        "id": "prj-123",
+                        "meta": { "default": true }
+                    }
+                }
+            })
+        );
+        let actual: Request<()> =
+            serde_json::from_value(serde_json::to_value(&request)?)?;
+        assert_eq!(actual, request);
+        let request = Request::<()>::builder("team-members").build();
+        assert_eq!(
+            serde_json::to_value(&request)?,
+            json!({ "data": { "type": "team-members" } })
+        );
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
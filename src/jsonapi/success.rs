@@ -1,4 +1,5 @@
 use {
+    super::included::Included,
     getset::{CopyGetters, Getters},
     serde::{de::DeserializeOwned, Deserialize, Serialize},
     std::{
@@ -91,16 +92,50 @@ pub struct Data<ATTRIBUTES, RELATIONSHIPS> {
 #[derive(
     PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
 )]
+#[getset(get = "pub with_prefix")]
 pub struct Success<ATTRIBUTES, RELATIONSHIPS> {
     data: Data<ATTRIBUTES, RELATIONSHIPS>,
+    /// resources that were requested with `include`
+    #[serde(default, skip_serializing_if = "Included::is_empty")]
+    included: Included,
     #[serde(skip_serializing_if = "Option::is_none")]
-    included: Option<Vec<Data<ATTRIBUTES, RELATIONSHIPS>>>,
+    links: Option<Links>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta: Option<Meta>,
+}
+/// This struct represents a successful JSON:API response whose primary data
+/// is an array of resources, i.e the response of a list endpoint.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+pub struct SuccessList<ATTRIBUTES, RELATIONSHIPS> {
+    data: Vec<Data<ATTRIBUTES, RELATIONSHIPS>>,
+    /// resources that were requested with `include`
+    #[serde(default, skip_serializing_if = "Included::is_empty")]
+    included: Included,
     #[serde(skip_serializing_if = "Option::is_none")]
     links: Option<Links>,
     #[serde(skip_serializing_if = "Option::is_none")]
     meta: Option<Meta>,
 }
 // ────────────────────────────────────────────────────────────
+impl<ATTRIBUTES, RELATIONSHIPS> Success<ATTRIBUTES, RELATIONSHIPS> {
+    /// consumes the response and returns its primary data
+    pub fn into_data(self) -> Data<ATTRIBUTES, RELATIONSHIPS> {
+        self.data
+    }
+}
+impl<ATTRIBUTES, RELATIONSHIPS> SuccessList<ATTRIBUTES, RELATIONSHIPS> {
+    /// consumes the response and returns its primary data
+    pub fn into_data(self) -> Vec<Data<ATTRIBUTES, RELATIONSHIPS>> {
+        self.data
+    }
+    /// returns pagination meta of the response, if any
+    pub fn get_pagination(&self) -> Option<&Pagination> {
+        self.meta.as_ref().and_then(|v| v.get_pagination().as_ref())
+    }
+}
 impl<ATTRIBUTES, RELATIONSHIPS> From<serde_json::Value>
     for Success<ATTRIBUTES, RELATIONSHIPS>
 where
@@ -124,6 +159,29 @@ where
         serde_json::to_value(arg).unwrap()
     }
 }
+impl<ATTRIBUTES, RELATIONSHIPS> From<serde_json::Value>
+    for SuccessList<ATTRIBUTES, RELATIONSHIPS>
+where
+    ATTRIBUTES: DeserializeOwned + Serialize,
+    RELATIONSHIPS: DeserializeOwned + Serialize,
+{
+    /// Convert a JSON Value into a SuccessList object
+    fn from(arg: serde_json::Value) -> Self {
+        serde_json::from_value::<SuccessList<ATTRIBUTES, RELATIONSHIPS>>(arg)
+            .unwrap()
+    }
+}
+impl<ATTRIBUTES, RELATIONSHIPS> From<SuccessList<ATTRIBUTES, RELATIONSHIPS>>
+    for serde_json::Value
+where
+    ATTRIBUTES: DeserializeOwned + Serialize,
+    RELATIONSHIPS: DeserializeOwned + Serialize,
+{
+    /// Convert a SuccessList object into a JSON Value
+    fn from(arg: SuccessList<ATTRIBUTES, RELATIONSHIPS>) -> Self {
+        serde_json::to_value(arg).unwrap()
+    }
+}
 #[cfg(test)]
 mod tests {
     use std::any::Any;
@@ -192,6 +250,50 @@ mod tests {
         assert_eq!(actual, expected);
         Ok(())
     }
+    #[test]
+    fn test_success_list_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs#pagination
+        let input = json!({
+            "data": [
+                {
+                    "id": "ws-SihZTyXKfNXUWuUa",
+                    "type": "workspaces",
+                    "attributes": { "name": "workspace-1" }
+                },
+                {
+                    "id": "ws-YnyXLq9fy38afEeb",
+                    "type": "workspaces",
+                    "attributes": { "name": "workspace-2" }
+                }
+            ],
+            "links": {
+                "self": "https://app.terraform.io/api/v2/organizations/hashicorp/workspaces?page%5Bnumber%5D=1&page%5Bsize%5D=2",
+                "next": "https://app.terraform.io/api/v2/organizations/hashicorp/workspaces?page%5Bnumber%5D=2&page%5Bsize%5D=2"
+            },
+            "meta": {
+                "pagination": {
+                    "current-page": 1,
+                    "next-page": 2,
+                    "total-pages": 2,
+                    "total-count": 3
+                }
+            }
+        });
+        let actual: crate::jsonapi::SuccessList<
+            serde_json::Value,
+            serde_json::Value,
+        > = input.clone().into();
+        assert_eq!(actual.get_data().len(), 2);
+        let pagination = actual.get_pagination().unwrap();
+        assert_eq!(*pagination.get_next_page(), Some(2));
+        assert_eq!(*pagination.get_total_count(), 3);
+        assert!(actual.get_links().as_ref().unwrap().get_next().is_some());
+        let output: serde_json::Value = actual.into();
+        assert_eq!(output["meta"], input["meta"]);
+        assert_eq!(output["data"], input["data"]);
+        Ok(())
+    }
 }
 // ────────────────────────────────────────────────────────────
 // vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
@@ -1,7 +1,57 @@
+/// This module implements the applies API.
+pub mod applies;
+/// This module implements the authenticated HCP Terraform/Enterprise API client.
+pub mod client;
+/// This module implements the configuration versions API.
+pub mod configuration_versions;
 // TODO: maybe this should be private
 pub mod core;
 // TODO: maybe this should be private
 pub mod jsonapi;
+/// This module implements tailing of plan and apply logs.
+pub mod logs;
+/// This module implements the organization memberships API.
+pub mod organization_memberships;
+/// This module implements the organizations API.
+pub mod organizations;
+/// This module implements automatic pagination of list endpoints.
+pub mod pagination;
+/// This module implements the plans API.
+pub mod plans;
+/// This module implements the projects API.
+pub mod projects;
+/// This module implements the runs API.
+pub mod runs;
+/// This module implements Terraform state format version 4.
+pub mod state;
+/// This module implements the state versions API.
+pub mod state_versions;
+/// This module implements the team access API.
+pub mod team_access;
+/// This module implements the team membership API.
+pub mod team_members;
+/// This module implements the team project access API.
+pub mod team_project_access;
+/// This module implements the teams API.
+pub mod teams;
+/// This module implements helpers shared by unit tests.
+#[cfg(test)]
+pub(crate) mod testing;
+/// This module implements the API token APIs.
+pub mod tokens;
+/// This module implements the users and account API.
+pub mod users;
+/// This module implements the variable sets API.
+pub mod variable_sets;
+/// This module implements the workspace variables API.
+pub mod variables;
+/// This module implements the workspaces API.
+pub mod workspaces;
+// ────────────────────────────────────────────────────────────
+/// This represents an authenticated HCP Terraform/Enterprise API client.
+pub type Client = client::Client;
+/// This is used to build an authenticated API client.
+pub type ClientBuilder = client::Builder;
 // ────────────────────────────────────────────────────────────
 // vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
 // code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,396 @@
+//! This module implements tailing of plan and apply logs.
+//!
+//! Logs are served by the archivist service. While a plan or an apply is
+//! running, its log grows and is read in chunks with the `limit` and
+//! `offset` query parameters. A complete log starts with an STX (`0x02`)
+//! byte and ends with an ETX (`0x03`) byte, which are removed from the
+//! yielded chunks.
+//!
+//! Log read URLs of plans and applies are only valid for about a minute, so
+//! they are read again before they expire and when the log server rejects
+//! them. As plans and applies that errored, were canceled or are unreachable
+//! may never write ETX, their status is checked whenever the log has no new
+//! bytes, and the tail ends once they reached a final status and the log was
+//! read one last time.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! use futures::StreamExt;
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = tfe_oxide::logs::TailOptions::default();
+//!     let plans = client.plans();
+//!     let stream = plans.tail_logs("plan-8F5JFydVYAmtTjET", &options);
+//!     futures::pin_mut!(stream);
+//!     while let Some(chunk) = stream.next().await {
+//!         print!("{}", String::from_utf8_lossy(&chunk.unwrap()));
+//!     }
+//! }
+//! ```
+use {
+    crate::{
+        client::Client,
+        core::Error,
+        runs::{Backoff, WaitOptions},
+    },
+    bytes::Bytes,
+    futures::Stream,
+    std::time::Duration,
+    tokio::time::Instant,
+};
+// ────────────────────────────────────────────────────────────
+/// marks the beginning of a log
+const STX: u8 = 0x02;
+/// marks the end of a log
+const ETX: u8 = 0x03;
+/// default maximum number of bytes that are read with one request
+pub const DEFAULT_LIMIT: usize = 64 * 1024;
+/// age after which log read URLs are read again, as they are valid for one
+/// minute
+const URL_MAX_AGE: Duration = Duration::from_secs(50);
+// ────────────────────────────────────────────────────────────
+/// This struct holds options of a log tail.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct TailOptions {
+    /// maximum number of bytes that are read with one request
+    pub limit: usize,
+    /// waiting time between two requests that returned no new bytes, and
+    /// maximum total waiting time
+    pub wait: WaitOptions,
+}
+impl Default for TailOptions {
+    fn default() -> Self {
+        TailOptions {
+            limit: DEFAULT_LIMIT,
+            wait: WaitOptions::default(),
+        }
+    }
+}
+/// This enum represents where a log tail reads its log read URL from.
+#[derive(Debug, Clone)]
+pub(crate) enum Source {
+    Plan(String),
+    Apply(String),
+    Url(String),
+}
+impl Source {
+    /// returns true if the log read URL can be read again, i.e the source
+    /// is a plan or an apply
+    fn is_refreshable(&self) -> bool {
+        !matches!(self, Source::Url(_))
+    }
+}
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Plan(id) => write!(f, "logs of plan `{id}`"),
+            Source::Apply(id) => write!(f, "logs of apply `{id}`"),
+            Source::Url(_) => write!(f, "log"),
+        }
+    }
+}
+/// This struct holds the state of a log tail between two requests.
+struct Tail<'a> {
+    client: &'a Client,
+    source: Source,
+    /// log read URL and the time it was read at
+    url: Option<(String, Instant)>,
+    options: TailOptions,
+    /// number of bytes that were read so far, including markers
+    offset: usize,
+    backoff: Backoff,
+    /// true once the plan or apply reached a final status, after which the
+    /// log is read one last time
+    finished: bool,
+    done: bool,
+}
+impl<'a> Tail<'a> {
+    /// reads the plan or apply again, which refreshes its log read URL.
+    /// Returns true if it reached a final status.
+    async fn refresh(&mut self) -> Result<bool, Error> {
+        let (url, finished) = match &self.source {
+            Source::Plan(id) => {
+                let plan = self.client.plans().read(id).await?.into_data();
+                let attributes = plan.attributes.unwrap_or_default();
+                (attributes.log_read_url, attributes.status.is_final())
+            }
+            Source::Apply(id) => {
+                let apply = self.client.applies().read(id).await?.into_data();
+                let attributes = apply.attributes.unwrap_or_default();
+                (attributes.log_read_url, attributes.status.is_final())
+            }
+            Source::Url(url) => (Some(url.clone()), false),
+        };
+        let url = url.filter(|url| !url.is_empty()).ok_or_else(|| {
+            Error::Deserialize {
+                path: "data.attributes.log-read-url".to_string(),
+                reason: format!("{} do not have a log read URL", self.source),
+            }
+        })?;
+        self.url = Some((url, Instant::now()));
+        Ok(finished)
+    }
+    /// reads the log from the current offset
+    async fn download(&mut self) -> Result<Bytes, Error> {
+        let stale = match &self.url {
+            Some((_, read_at)) => {
+                self.source.is_refreshable()
+                    && read_at.elapsed() >= URL_MAX_AGE
+            }
+            None => true,
+        };
+        if stale {
+            self.refresh().await?;
+        }
+        let limit = self.options.limit.to_string();
+        let offset = self.offset.to_string();
+        let query =
+            [("limit".to_string(), limit), ("offset".to_string(), offset)];
+        let query = crate::core::query(&query);
+        let url = self.url.as_ref().map(|(url, _)| url.clone());
+        let url = url.unwrap_or_default();
+        match self.client.download(url.as_str(), Some(&query)).await {
+            // NOTE: the log read URL expired before it was expected to
+            Err(e)
+                if self.source.is_refreshable()
+                    && e.status().map_or(false, |v| v.is_client_error()) =>
+            {
+                tracing::debug!("\nlog read URL of {} expired", self.source);
+                self.refresh().await?;
+                let url = self.url.as_ref().map(|(url, _)| url.as_str());
+                self.client
+                    .download(url.unwrap_or_default(), Some(&query))
+                    .await
+            }
+            res => res,
+        }
+    }
+    /// reads the log until new bytes are available and returns them.
+    /// Returns `None` once the end of the log is reached.
+    async fn next(&mut self) -> Option<Result<Bytes, Error>> {
+        loop {
+            if let Err(elapsed) = self.backoff.wait().await {
+                return Some(Err(Error::Timeout {
+                    reason: format!(
+                        "timed out after {}ms waiting for the end of {}",
+                        elapsed.as_millis(),
+                        self.source
+                    ),
+                }));
+            }
+            let chunk = match self.download().await {
+                Ok(chunk) => chunk,
+                Err(e) => return Some(Err(e)),
+            };
+            if chunk.is_empty() {
+                if self.finished {
+                    tracing::debug!("\n{} ended without ETX", self.source);
+                    return None;
+                }
+                // NOTE: the log is read once more right away after the plan
+                // or apply reached a final status, as it may have written
+                // its last bytes in the meantime
+                match self.refresh().await {
+                    Ok(true) => {
+                        self.finished = true;
+                        self.backoff.reset();
+                    }
+                    Ok(false) => self.backoff.increase(),
+                    Err(e) => return Some(Err(e)),
+                }
+                continue;
+            }
+            self.backoff.reset();
+            let first = self.offset == 0;
+            self.offset += chunk.len();
+            let (chunk, done) = strip_markers(chunk, first);
+            self.done = done;
+            if !chunk.is_empty() {
+                return Some(Ok(chunk));
+            }
+            if done {
+                return None;
+            }
+        }
+    }
+}
+/// removes the STX byte from the first chunk of a log and the ETX byte from
+/// its last chunk. Also returns whether the chunk ends the log.
+fn strip_markers(mut chunk: Bytes, first: bool) -> (Bytes, bool) {
+    if first && chunk.first() == Some(&STX) {
+        chunk = chunk.slice(1..);
+    }
+    let done = chunk.last() == Some(&ETX);
+    if done {
+        chunk.truncate(chunk.len() - 1);
+    }
+    (chunk, done)
+}
+/// returns a stream of log chunks of the source
+pub(crate) fn stream<'a>(
+    client: &'a Client,
+    source: Source,
+    options: &TailOptions,
+) -> impl Stream<Item = Result<Bytes, Error>> + 'a {
+    let state = Tail {
+        client,
+        source,
+        url: None,
+        options: options.clone(),
+        offset: 0,
+        backoff: Backoff::new(options.wait.clone()),
+        finished: false,
+        done: false,
+    };
+    futures::stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+        let res = state.next().await?;
+        if res.is_err() {
+            state.done = true;
+        }
+        Some((res, state))
+    })
+}
+impl Client {
+    /// tails a log and returns its chunks as they become available. The
+    /// stream ends once the end of the log is reached, and after the first
+    /// error.
+    ///
+    /// ## Parameters
+    /// * `url` : log read URL of a plan or an apply
+    /// * `options` : chunk size, polling intervals and deadline
+    pub fn tail_logs<'a>(
+        &'a self,
+        url: &str,
+        options: &TailOptions,
+    ) -> impl Stream<Item = Result<Bytes, Error>> + 'a {
+        stream(self, Source::Url(url.to_string()), options)
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "logs::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "logs::tests" --nocapture'
+    use {
+        super::*,
+        crate::testing::{Reply, Server},
+        futures::StreamExt,
+        serde_json::json,
+    };
+    /// returns a plan response with the given status and log read URL
+    fn plan(status: &str, log_read_url: &str) -> Reply {
+        Reply::json(json!({
+            "data": {
+                "id": "plan-8F5JFydVYAmtTjET",
+                "type": "plans",
+                "attributes": {
+                    "status": status,
+                    "log-read-url": log_read_url
+                }
+            }
+        }))
+    }
+    /// returns tail options that poll every millisecond
+    fn options() -> TailOptions {
+        TailOptions {
+            wait: WaitOptions {
+                interval: Duration::from_millis(1),
+                max_interval: Duration::from_millis(1),
+                deadline: Some(Duration::from_secs(5)),
+            },
+            ..Default::default()
+        }
+    }
+    #[test]
+    fn test_strip_markers() {
+        let chunk = Bytes::from_static(b"\x02Terraform v1.5.0\n\x03");
+        let (chunk, done) = strip_markers(chunk, true);
+        assert_eq!(chunk, Bytes::from_static(b"Terraform v1.5.0\n"));
+        assert!(done);
+        // NOTE: STX is only a marker at the beginning of the log
+        let chunk = Bytes::from_static(b"\x02plan");
+        let (chunk, done) = strip_markers(chunk, false);
+        assert_eq!(chunk, Bytes::from_static(b"\x02plan"));
+        assert!(!done);
+        let (chunk, done) = strip_markers(Bytes::from_static(b"\x03"), false);
+        assert!(chunk.is_empty());
+        assert!(done);
+    }
+    #[tokio::test]
+    async fn test_tail_refreshes_expired_url() {
+        // NOTE: relative log read URLs are sent to the test server
+        let server = Server::start(vec![
+            plan("running", "logs/expired"),
+            Reply::status(403, ""),
+            plan("running", "logs/fresh"),
+            Reply::status(200, "\u{2}Terraform v1.5.0\n\u{3}"),
+        ])
+        .await;
+        let client = server.client();
+        let stream = client
+            .plans()
+            .tail_logs("plan-8F5JFydVYAmtTjET", &options());
+        let actual: Vec<_> = stream.collect().await;
+        assert_eq!(
+            actual,
+            vec![Ok(Bytes::from_static(b"Terraform v1.5.0\n"))]
+        );
+        let targets: Vec<_> =
+            server.received().into_iter().map(|v| v.target).collect();
+        assert_eq!(targets[0], "/api/v2/plans/plan-8F5JFydVYAmtTjET");
+        assert!(targets[1].starts_with("/api/v2/logs/expired?"));
+        assert_eq!(targets[2], "/api/v2/plans/plan-8F5JFydVYAmtTjET");
+        assert!(targets[3].starts_with("/api/v2/logs/fresh?"));
+    }
+    #[tokio::test]
+    async fn test_tail_ends_when_plan_errored() {
+        let server = Server::start(vec![
+            plan("running", "logs/plan"),
+            Reply::status(200, "\u{2}Error: invalid"),
+            Reply::status(200, ""),
+            plan("errored", "logs/plan"),
+            // NOTE: the log is read one last time, and it never ends with ETX
+            Reply::status(200, ""),
+        ])
+        .await;
+        let client = server.client();
+        let stream = client
+            .plans()
+            .tail_logs("plan-8F5JFydVYAmtTjET", &options());
+        let actual: Vec<_> = stream.collect().await;
+        assert_eq!(actual, vec![Ok(Bytes::from_static(b"Error: invalid"))]);
+        let received = server.received();
+        assert_eq!(received.len(), 5);
+        assert!(received[4].target.contains("offset=15"));
+    }
+    #[tokio::test]
+    async fn test_tail_deadline() {
+        let server = Server::start(vec![
+            plan("running", "logs/plan"),
+            Reply::status(200, ""),
+            plan("running", "logs/plan"),
+            Reply::status(200, ""),
+            plan("running", "logs/plan"),
+        ])
+        .await;
+        let client = server.client();
+        let mut options = options();
+        options.wait.interval = Duration::from_millis(50);
+        options.wait.max_interval = Duration::from_millis(50);
+        options.wait.deadline = Some(Duration::from_millis(30));
+        let stream =
+            client.plans().tail_logs("plan-8F5JFydVYAmtTjET", &options);
+        let actual: Vec<_> = stream.collect().await;
+        assert_eq!(actual.len(), 1);
+        assert!(actual[0].as_ref().unwrap_err().is_timeout());
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,372 @@
+//! This module implements the organization memberships API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/organization-memberships).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let memberships = client.organization_memberships();
+//!     let options = tfe_oxide::organization_memberships::ListOptions {
+//!         emails: vec!["leaver@example.com".to_string()],
+//!         ..Default::default()
+//!     };
+//!     for membership in memberships
+//!         .list("my-organization", &options)
+//!         .await
+//!         .unwrap()
+//!     {
+//!         memberships
+//!             .delete(membership.get_id().as_deref().unwrap())
+//!             .await
+//!             .unwrap();
+//!     }
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, One, Relationship},
+        pagination::PaginatorOptions,
+    },
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents an organization membership resource.
+pub type Membership = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single organization
+/// membership.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents the state of an organization membership.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    /// the user has been invited but has not accepted the invitation yet
+    Invited,
+    /// the user is a member of the organization
+    Active,
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+impl std::fmt::Display for Status {
+    /// prints the status the way the API represents it, e.g `invited`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = serde_json::to_value(self).unwrap_or_default();
+        write!(f, "{}", value.as_str().unwrap_or("unknown"))
+    }
+}
+/// This struct represents attributes of an organization membership.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+/// This struct represents relationships of an organization membership.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub teams: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing organization memberships.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of memberships per page
+    pub page_size: Option<u32>,
+    /// search by username or email
+    pub query: Option<String>,
+    /// only return memberships of users with the given email addresses
+    pub emails: Vec<String>,
+    /// only return memberships in the given state
+    pub status: Option<Status>,
+    /// related resources to include, i.e `user` or `teams`
+    pub include: Vec<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut res = crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+            ("q", self.query.clone()),
+            ("filter[status]", self.status.map(|v| v.to_string())),
+        ]);
+        if !self.emails.is_empty() {
+            res.push(("filter[email]".to_string(), self.emails.join(",")));
+        }
+        if !self.include.is_empty() {
+            res.push(("include".to_string(), self.include.join(",")));
+        }
+        res
+    }
+}
+/// This struct contains options for reading an organization membership.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// related resources to include, i.e `user` or `teams`
+    pub include: Vec<String>,
+}
+impl ReadOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        if self.include.is_empty() {
+            return Vec::new();
+        }
+        vec![("include".to_string(), self.include.join(","))]
+    }
+}
+/// This struct contains options for inviting a user to an organization.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    /// email address the invitation is sent to
+    pub email: String,
+    /// teams the user is added to once the invitation is accepted
+    #[serde(skip)]
+    pub team_ids: Vec<String>,
+}
+// ────────────────────────────────────────────────────────────
+/// returns request document for inviting a user to an organization
+fn create_request(
+    options: &CreateOptions,
+) -> jsonapi::Request<&CreateOptions> {
+    let mut request = jsonapi::Request::builder("organization-memberships")
+        .attributes(options);
+    if !options.team_ids.is_empty() {
+        request = request.to_many("teams", "teams", &options.team_ids);
+    }
+    request.build()
+}
+// ────────────────────────────────────────────────────────────
+/// OrganizationMemberships implements the organization memberships API.
+#[derive(Debug, Clone)]
+pub struct OrganizationMemberships<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns organization memberships API
+    pub fn organization_memberships(&self) -> OrganizationMemberships<'_> {
+        OrganizationMemberships { client: self }
+    }
+}
+impl<'a> OrganizationMemberships<'a> {
+    /// lists memberships of an organization
+    pub async fn list(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Membership>, Error> {
+        validate_id("organization", organization)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                format!(
+                    "organizations/{organization}/organization-memberships"
+                ),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns memberships of an organization across all pages. The stream
+    /// ends after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<Membership, Error>>, Error> {
+        validate_id("organization", organization)?;
+        Ok(self.client.paginate(
+            &format!("organizations/{organization}/organization-memberships"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// invites a user to an organization
+    pub async fn create(
+        &self,
+        organization: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        if !options.email.contains('@') {
+            return Err(Error::InvalidInput {
+                reason: format!(
+                    "invalid value for email: `{}`",
+                    options.email
+                ),
+            });
+        }
+        for id in &options.team_ids {
+            validate_id("team ID", id)?;
+        }
+        self.client
+            .post(
+                format!(
+                    "organizations/{organization}/organization-memberships"
+                ),
+                None,
+                create_request(options),
+            )
+            .await
+    }
+    /// reads an organization membership
+    pub async fn read(
+        &self,
+        membership_id: &str,
+        options: &ReadOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization membership ID", membership_id)?;
+        let query = options.to_query();
+        self.client
+            .get(
+                format!("organization-memberships/{membership_id}"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await
+    }
+    /// removes a user from an organization, or revokes an invitation
+    pub async fn delete(&self, membership_id: &str) -> Result<(), Error> {
+        validate_id("organization membership ID", membership_id)?;
+        self.client
+            .delete(
+                format!("organization-memberships/{membership_id}"),
+                None,
+                (),
+            )
+            .await
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "organization_memberships::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "organization_memberships::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/organization-memberships
+        let input = json!({
+            "data": {
+                "id": "ou-nX7inDHhmC3quYgy",
+                "type": "organization-memberships",
+                "attributes": {
+                    "status": "invited",
+                    "email": "test@example.com"
+                },
+                "relationships": {
+                    "teams": {
+                        "data": [{ "id": "team-GeLZkdnK6xAVjA5H", "type": "teams" }]
+                    },
+                    "user": {
+                        "data": { "id": "user-J8oxGmRk5eC2WLfX", "type": "users" }
+                    },
+                    "organization": {
+                        "data": { "id": "my-organization", "type": "organizations" }
+                    }
+                }
+            },
+            "included": [
+                {
+                    "id": "user-J8oxGmRk5eC2WLfX",
+                    "type": "users",
+                    "attributes": {
+                        "username": null,
+                        "is-service-account": false,
+                        "email": "test@example.com"
+                    }
+                }
+            ]
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.status, Status::Invited);
+        assert_eq!(attributes.email.as_deref(), Some("test@example.com"));
+        Ok(())
+    }
+    #[test]
+    fn test_list_options_to_query() {
+        let options = ListOptions {
+            emails: vec!["a@example.com".into(), "b@example.com".into()],
+            status: Some(Status::Active),
+            ..Default::default()
+        };
+        let expected = vec![
+            ("filter[status]".to_string(), "active".to_string()),
+            (
+                "filter[email]".to_string(),
+                "a@example.com,b@example.com".to_string(),
+            ),
+        ];
+        assert_eq!(options.to_query(), expected);
+    }
+    #[test]
+    fn test_create_request() {
+        let mut options = CreateOptions {
+            email: "user@example.com".to_string(),
+            team_ids: Vec::new(),
+        };
+        let expected = json!({
+            "data": {
+                "type": "organization-memberships",
+                "attributes": { "email": "user@example.com" }
+            }
+        });
+        assert_eq!(
+            serde_json::to_value(create_request(&options)).unwrap(),
+            expected
+        );
+        options.team_ids = vec!["team-123".to_string()];
+        let expected = json!({
+            "data": {
+                "type": "organization-memberships",
+                "attributes": { "email": "user@example.com" },
+                "relationships": {
+                    "teams": {
+                        "data": [{ "type": "teams", "id": "team-123" }]
+                    }
+                }
+            }
+        });
+        assert_eq!(
+            serde_json::to_value(create_request(&options)).unwrap(),
+            expected
+        );
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
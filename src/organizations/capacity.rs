@@ -0,0 +1,40 @@
+//! This module implements the run capacity of organizations.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/organizations#show-capacity).
+use {
+    crate::jsonapi,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents an organization capacity resource.
+pub type Capacity = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains the capacity of an
+/// organization.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This struct represents the number of runs an organization has in flight.
+#[derive(
+    PartialEq, Eq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    /// number of runs waiting for a free slot
+    #[serde(default)]
+    pub pending: u32,
+    /// number of runs in progress
+    #[serde(default)]
+    pub running: u32,
+}
+/// This struct represents relationships of an organization capacity.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,116 @@
+//! This module implements organization entitlement sets.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/organizations#show-the-entitlement-set).
+use {
+    crate::jsonapi,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents an entitlement set resource.
+pub type Entitlements = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains the entitlement set of an
+/// organization.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This struct represents features an organization is entitled to.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    #[serde(default)]
+    pub agents: bool,
+    #[serde(default)]
+    pub audit_logging: bool,
+    #[serde(default)]
+    pub configuration_designer: bool,
+    #[serde(default)]
+    pub cost_estimation: bool,
+    #[serde(default)]
+    pub global_run_tasks: bool,
+    #[serde(default)]
+    pub module_tests_generation: bool,
+    #[serde(default)]
+    pub operations: bool,
+    #[serde(default)]
+    pub private_module_registry: bool,
+    #[serde(default)]
+    pub private_policy_agents: bool,
+    #[serde(default)]
+    pub private_vcs: bool,
+    #[serde(default)]
+    pub run_tasks: bool,
+    #[serde(default)]
+    pub self_serve_billing: bool,
+    #[serde(default)]
+    pub sentinel: bool,
+    #[serde(default)]
+    pub sso: bool,
+    #[serde(default)]
+    pub state_storage: bool,
+    #[serde(default)]
+    pub teams: bool,
+    #[serde(default)]
+    pub usage_reporting: bool,
+    /// maximum number of users, if the organization is limited
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_limit: Option<u32>,
+    #[serde(default)]
+    pub vcs_integrations: bool,
+}
+/// This struct represents relationships of an entitlement set.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "organizations::entitlements::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "organizations::entitlements::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/organizations#show-the-entitlement-set
+        let input = json!({
+            "data": {
+                "id": "org-Bzyc2JuegvVLAibn",
+                "type": "entitlement-sets",
+                "attributes": {
+                    "cost-estimation": true,
+                    "configuration-designer": true,
+                    "operations": true,
+                    "private-module-registry": true,
+                    "sentinel": false,
+                    "run-tasks": false,
+                    "state-storage": true,
+                    "teams": false,
+                    "vcs-integrations": true,
+                    "usage-reporting": false,
+                    "user-limit": 5,
+                    "self-serve-billing": true,
+                    "audit-logging": false,
+                    "agents": false,
+                    "sso": false
+                },
+                "links": {
+                    "self": "/api/v2/entitlement-sets/org-Bzyc2JuegvVLAibn"
+                }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert!(attributes.cost_estimation);
+        assert!(!attributes.sentinel);
+        assert_eq!(attributes.user_limit, Some(5));
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
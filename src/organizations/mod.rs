@@ -0,0 +1,651 @@
+//! This module implements the organizations API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/organizations).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let entitlements = client
+//!         .organizations()
+//!         .read_entitlements("my-organization")
+//!         .await
+//!         .unwrap()
+//!         .into_data();
+//!     println!("{:?}", entitlements.get_attributes());
+//! }
+//! ```
+/// This module implements the run capacity of organizations.
+pub mod capacity;
+/// This module implements organization entitlement sets.
+pub mod entitlements;
+// ────────────────────────────────────────────────────────────
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, One, Relationship},
+        pagination::PaginatorOptions,
+        runs,
+    },
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents an organization resource.
+pub type Organization = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single organization.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents the authentication policy of an organization.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum CollaboratorAuthPolicy {
+    /// users can log in with a password only
+    #[default]
+    Password,
+    /// users must enable two factor authentication
+    TwoFactorMandatory,
+    #[serde(other)]
+    Unknown,
+}
+/// This enum represents where runs of a workspace are executed.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionMode {
+    /// runs are executed by HCP Terraform/Enterprise
+    #[default]
+    Remote,
+    /// runs are executed on the machine of the user and only the state is
+    /// stored remotely
+    Local,
+    /// runs are executed by an agent pool of the organization
+    Agent,
+    #[serde(other)]
+    Unknown,
+}
+/// This struct represents permissions the current API token has on an
+/// organization.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Permissions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_access_via_teams: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_create_module: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_create_team: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_create_workspace: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_destroy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_manage_run_tasks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_manage_subscription: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_manage_users: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_read_run_tasks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_oauth: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_sentinel: Option<bool>,
+}
+/// This struct represents attributes of an organization.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub name: String,
+    /// notification email address of the organization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregated_commit_status_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_force_delete_workspaces: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assessments_enforced: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collaborator_auth_policy: Option<CollaboratorAuthPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_estimation_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_execution_mode: Option<ExecutionMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fair_run_queuing_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owners_team_saml_role_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_expired: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_expires_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_is_enterprise: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_is_trial: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub saml_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_passing_statuses_for_untriggered_speculative_plans: Option<bool>,
+    /// minutes of inactivity after which a session expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_remember: Option<u32>,
+    /// minutes after which a session expires
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_timeout: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trial_expires_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub two_factor_conformant: Option<bool>,
+}
+/// This struct represents relationships of an organization.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication_token: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_agent_pool: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_project: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub entitlement_set: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oauth_tokens: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subscription: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing organizations.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of organizations per page
+    pub page_size: Option<u32>,
+    /// search by name or notification email
+    pub query: Option<String>,
+    /// search by notification email
+    pub query_email: Option<String>,
+    /// search by name
+    pub query_name: Option<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+            ("q", self.query.clone()),
+            ("q[email]", self.query_email.clone()),
+            ("q[name]", self.query_name.clone()),
+        ])
+    }
+}
+/// This struct contains options for listing the run queue of an
+/// organization.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct RunQueueOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of runs per page
+    pub page_size: Option<u32>,
+}
+impl RunQueueOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+        ])
+    }
+}
+/// This struct contains options for creating an organization.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    /// name of the organization, which must be unique
+    pub name: String,
+    /// notification email address of the organization
+    pub email: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregated_commit_status_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_force_delete_workspaces: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assessments_enforced: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collaborator_auth_policy: Option<CollaboratorAuthPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_estimation_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_execution_mode: Option<ExecutionMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owners_team_saml_role_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_passing_statuses_for_untriggered_speculative_plans: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_remember: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_timeout: Option<u32>,
+}
+/// This struct contains options for updating an organization. Unset fields
+/// are left unchanged.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateOptions {
+    /// new name of the organization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregated_commit_status_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_force_delete_workspaces: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assessments_enforced: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub collaborator_auth_policy: Option<CollaboratorAuthPolicy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_estimation_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default_execution_mode: Option<ExecutionMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owners_team_saml_role_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub send_passing_statuses_for_untriggered_speculative_plans: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_remember: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_timeout: Option<u32>,
+}
+/// returns an error if a policy or an execution mode is unknown, as such
+/// values are only meant to be read from the API
+fn validate_modes(
+    collaborator_auth_policy: Option<CollaboratorAuthPolicy>,
+    default_execution_mode: Option<ExecutionMode>,
+) -> Result<(), Error> {
+    if collaborator_auth_policy == Some(CollaboratorAuthPolicy::Unknown) {
+        return Err(Error::InvalidInput {
+            reason: "unknown collaborator auth policy".to_string(),
+        });
+    }
+    if default_execution_mode == Some(ExecutionMode::Unknown) {
+        return Err(Error::InvalidInput {
+            reason: "unknown default execution mode".to_string(),
+        });
+    }
+    Ok(())
+}
+// ────────────────────────────────────────────────────────────
+/// Organizations implements the organizations API.
+#[derive(Debug, Clone)]
+pub struct Organizations<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns organizations API
+    pub fn organizations(&self) -> Organizations<'_> {
+        Organizations { client: self }
+    }
+}
+impl<'a> Organizations<'a> {
+    /// lists organizations the current API token has access to
+    pub async fn list(
+        &self,
+        options: &ListOptions,
+    ) -> Result<Vec<Organization>, Error> {
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                "organizations".to_string(),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns organizations the current API token has access to across all
+    /// pages. The stream ends after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<Organization, Error>>, Error> {
+        Ok(self.client.paginate(
+            "organizations",
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// creates an organization
+    pub async fn create(
+        &self,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", options.name.as_str())?;
+        if options.email.is_empty() {
+            return Err(Error::InvalidInput {
+                reason: "organization email is required".to_string(),
+            });
+        }
+        validate_modes(
+            options.collaborator_auth_policy,
+            options.default_execution_mode,
+        )?;
+        let payload = jsonapi::Request::builder("organizations")
+            .attributes(options)
+            .build();
+        self.client
+            .post("organizations".to_string(), None, payload)
+            .await
+    }
+    /// reads an organization
+    pub async fn read(&self, organization: &str) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        self.client
+            .get(format!("organizations/{organization}"), None, None)
+            .await
+    }
+    /// updates an organization
+    pub async fn update(
+        &self,
+        organization: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        validate_modes(
+            options.collaborator_auth_policy,
+            options.default_execution_mode,
+        )?;
+        let payload = jsonapi::Request::builder("organizations")
+            .attributes(options)
+            .build();
+        self.client
+            .patch(format!("organizations/{organization}"), None, payload)
+            .await
+    }
+    /// deletes an organization
+    pub async fn delete(&self, organization: &str) -> Result<(), Error> {
+        validate_id("organization", organization)?;
+        self.client
+            .delete(format!("organizations/{organization}"), None, ())
+            .await
+    }
+    /// reads the features an organization is entitled to
+    pub async fn read_entitlements(
+        &self,
+        organization: &str,
+    ) -> Result<entitlements::Response, Error> {
+        validate_id("organization", organization)?;
+        self.client
+            .get(
+                format!("organizations/{organization}/entitlement-set"),
+                None,
+                None,
+            )
+            .await
+    }
+    /// reads the number of pending and running runs of an organization
+    pub async fn read_capacity(
+        &self,
+        organization: &str,
+    ) -> Result<capacity::Response, Error> {
+        validate_id("organization", organization)?;
+        self.client
+            .get(format!("organizations/{organization}/capacity"), None, None)
+            .await
+    }
+    /// lists runs of an organization that are queued or in progress
+    pub async fn read_run_queue(
+        &self,
+        organization: &str,
+        options: &RunQueueOptions,
+    ) -> Result<Vec<runs::Run>, Error> {
+        validate_id("organization", organization)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<
+            runs::Attributes,
+            runs::Relationships,
+        > = self
+            .client
+            .get(
+                format!("organizations/{organization}/runs/queue"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "organizations::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "organizations::tests" --nocapture'
+    use {
+        super::*,
+        crate::testing::{Reply, Server},
+        serde_json::json,
+    };
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/organizations
+        let input = json!({
+            "data": {
+                "id": "hashicorp",
+                "type": "organizations",
+                "attributes": {
+                    "external-id": "org-Bzyc2JuegvVLAibn",
+                    "created-at": "2021-08-30T18:09:57.561Z",
+                    "email": "user@example.com",
+                    "session-timeout": null,
+                    "session-remember": null,
+                    "collaborator-auth-policy": "password",
+                    "plan-expired": false,
+                    "plan-expires-at": null,
+                    "plan-is-trial": false,
+                    "plan-is-enterprise": false,
+                    "cost-estimation-enabled": false,
+                    "send-passing-statuses-for-untriggered-speculative-plans": false,
+                    "allow-force-delete-workspaces": false,
+                    "assessments-enforced": false,
+                    "default-execution-mode": "remote",
+                    "name": "hashicorp",
+                    "permissions": {
+                        "can-update": true,
+                        "can-destroy": true,
+                        "can-create-team": false
+                    },
+                    "fair-run-queuing-enabled": true,
+                    "saml-enabled": false,
+                    "owners-team-saml-role-id": null,
+                    "two-factor-conformant": false
+                },
+                "relationships": {
+                    "default-project": {
+                        "data": { "id": "prj-nL3Fv5tu2zF3W4zF", "type": "projects" }
+                    },
+                    "oauth-tokens": {
+                        "links": { "related": "/api/v2/organizations/hashicorp/oauth-tokens" }
+                    },
+                    "entitlement-set": {
+                        "data": { "id": "org-Bzyc2JuegvVLAibn", "type": "entitlement-sets" }
+                    }
+                },
+                "links": { "self": "/api/v2/organizations/hashicorp" }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.name, "hashicorp");
+        assert_eq!(
+            attributes.collaborator_auth_policy,
+            Some(CollaboratorAuthPolicy::Password)
+        );
+        assert_eq!(
+            attributes.default_execution_mode,
+            Some(ExecutionMode::Remote)
+        );
+        Ok(())
+    }
+    #[test]
+    fn test_update_options_serialize() -> Result<(), serde_json::Error> {
+        let options = UpdateOptions {
+            collaborator_auth_policy: Some(
+                CollaboratorAuthPolicy::TwoFactorMandatory,
+            ),
+            default_execution_mode: Some(ExecutionMode::Agent),
+            assessments_enforced: Some(true),
+            ..Default::default()
+        };
+        let expected = json!({
+            "collaborator-auth-policy": "two_factor_mandatory",
+            "default-execution-mode": "agent",
+            "assessments-enforced": true
+        });
+        assert_eq!(serde_json::to_value(&options)?, expected);
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_unknown_modes_are_rejected() {
+        let server = Server::start(vec![]).await;
+        let client = server.client();
+        let options = CreateOptions {
+            name: "my-organization".to_string(),
+            email: "admin@example.com".to_string(),
+            collaborator_auth_policy: Some(CollaboratorAuthPolicy::Unknown),
+            ..Default::default()
+        };
+        let actual = client.organizations().create(&options).await;
+        assert!(actual.unwrap_err().is_invalid_input());
+        let options = UpdateOptions {
+            default_execution_mode: Some(ExecutionMode::Unknown),
+            ..Default::default()
+        };
+        let actual = client
+            .organizations()
+            .update("my-organization", &options)
+            .await;
+        assert!(actual.unwrap_err().is_invalid_input());
+        assert!(server.received().is_empty());
+    }
+    #[tokio::test]
+    async fn test_read_capacity() {
+        let server = Server::start(vec![Reply::json(json!({
+            "data": {
+                "id": "my-organization",
+                "type": "organization-capacity",
+                "attributes": { "pending": 1, "running": 2 }
+            }
+        }))])
+        .await;
+        let client = server.client();
+        let actual = client
+            .organizations()
+            .read_capacity("my-organization")
+            .await
+            .unwrap();
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!((attributes.pending, attributes.running), (1, 2));
+        let received = server.received();
+        assert_eq!(received[0].method, "GET");
+        assert_eq!(
+            received[0].target,
+            "/api/v2/organizations/my-organization/capacity"
+        );
+    }
+    #[tokio::test]
+    async fn test_read_entitlements() {
+        let server = Server::start(vec![Reply::json(json!({
+            "data": {
+                "id": "org-123",
+                "type": "entitlement-sets",
+                "attributes": { "agents": true, "user-limit": 5 }
+            }
+        }))])
+        .await;
+        let client = server.client();
+        let actual = client
+            .organizations()
+            .read_entitlements("my-organization")
+            .await
+            .unwrap();
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert!(attributes.agents);
+        assert!(!attributes.sso);
+        assert_eq!(attributes.user_limit, Some(5));
+        let received = server.received();
+        assert_eq!(received[0].method, "GET");
+        assert_eq!(
+            received[0].target,
+            "/api/v2/organizations/my-organization/entitlement-set"
+        );
+    }
+    #[tokio::test]
+    async fn test_read_run_queue() {
+        let server = Server::start(vec![Reply::json(json!({
+            "data": [{
+                "id": "run-123",
+                "type": "runs",
+                "attributes": { "status": "pending" }
+            }]
+        }))])
+        .await;
+        let client = server.client();
+        let options = RunQueueOptions {
+            page_number: Some(2),
+            page_size: Some(50),
+        };
+        let actual = client
+            .organizations()
+            .read_run_queue("my-organization", &options)
+            .await
+            .unwrap();
+        assert_eq!(actual.len(), 1);
+        assert_eq!(actual[0].id.as_deref(), Some("run-123"));
+        assert_eq!(
+            actual[0].attributes.as_ref().map(|v| v.status),
+            Some(runs::Status::Pending)
+        );
+        let received = server.received();
+        assert_eq!(received[0].method, "GET");
+        let (path, query) = received[0].target.split_once('?').unwrap();
+        assert_eq!(path, "/api/v2/organizations/my-organization/runs/queue");
+        assert!(query.contains("page%5Bnumber%5D=2"));
+        assert!(query.contains("page%5Bsize%5D=50"));
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
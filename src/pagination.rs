@@ -0,0 +1,464 @@
+//! This module implements automatic pagination of list endpoints.
+//!
+//! List endpoints return one page of resources at a time. The paginator
+//! requests the pages one after the other and yields their resources as a
+//! single stream. The next page is found with `meta.pagination.next-page`,
+//! or with `links.next` for endpoints that do not return pagination meta.
+//!
+//! Services expose the paginator through their `list_all` methods. Any other
+//! list endpoint can be paginated by passing its path and the query
+//! parameters of its list options to `Client::paginate`.
+//!
+//! SECURITY: `links.next` is only followed when it points to the configured
+//! API address, as the API token is sent along. Otherwise the stream ends
+//! with an error.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! use {futures::StreamExt, tfe_oxide::workspaces};
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = tfe_oxide::pagination::PaginatorOptions {
+//!         page_size: Some(100),
+//!         ..Default::default()
+//!     };
+//!     let list_options = workspaces::ListOptions::default();
+//!     let stream = client
+//!         .workspaces()
+//!         .list_all("my-organization", &list_options, &options)
+//!         .unwrap();
+//!     futures::pin_mut!(stream);
+//!     while let Some(workspace) = stream.next().await {
+//!         println!("{:?}", workspace.unwrap().get_id());
+//!     }
+//! }
+//! ```
+use {
+    crate::{
+        client::Client,
+        core::{Error, HttpClient},
+        jsonapi::{self, SuccessList},
+    },
+    futures::Stream,
+    serde::{de::DeserializeOwned, Serialize},
+    std::{collections::VecDeque, fmt::Debug},
+    tokio::task::JoinHandle,
+};
+// ────────────────────────────────────────────────────────────
+/// maximum page size the API accepts
+pub const MAX_PAGE_SIZE: u32 = 100;
+// ────────────────────────────────────────────────────────────
+/// This struct holds options of a paginator.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct PaginatorOptions {
+    /// number of resources per page. The API default is used when unset.
+    pub page_size: Option<u32>,
+    /// maximum number of resources to yield. Pages past it are not
+    /// requested.
+    pub max_items: Option<usize>,
+    /// requests the next page in the background while the resources of the
+    /// current page are consumed
+    pub prefetch: bool,
+}
+/// This enum represents how the next page is requested.
+#[derive(PartialEq, Eq, Debug, Clone)]
+enum Next {
+    /// page number, sent as `page[number]` along with the list query
+    Page(u32),
+    /// URL of `links.next`, which already has the query
+    Url(String),
+}
+/// This struct holds the state of a paginator between two pages.
+struct Paginator<A, R> {
+    client: Client,
+    path: String,
+    /// list query without page parameters
+    query: Vec<(String, String)>,
+    options: PaginatorOptions,
+    /// resources of the current page that were not yielded yet
+    items: VecDeque<jsonapi::Data<A, R>>,
+    next: Option<Next>,
+    /// next page that is being requested in the background
+    pending: Option<JoinHandle<Result<SuccessList<A, R>, Error>>>,
+    yielded: usize,
+    done: bool,
+}
+impl<A, R> Paginator<A, R>
+where
+    A: DeserializeOwned + Serialize + Debug + Send + 'static,
+    R: DeserializeOwned + Serialize + Debug + Send + 'static,
+{
+    /// returns the next resource. Returns `None` once all pages were read or
+    /// the maximum number of resources was yielded.
+    async fn next(&mut self) -> Option<Result<jsonapi::Data<A, R>, Error>> {
+        loop {
+            if let Some(max_items) = self.options.max_items {
+                if self.yielded >= max_items {
+                    return None;
+                }
+            }
+            if let Some(item) = self.items.pop_front() {
+                self.yielded += 1;
+                return Some(Ok(item));
+            }
+            let page = match (self.pending.take(), self.next.take()) {
+                (Some(pending), _) => pending.await.unwrap_or_else(|e| {
+                    Err(Error::Transport {
+                        reason: format!(
+                            "failed to request the next page. {e}"
+                        ),
+                    })
+                }),
+                (None, Some(next)) => self.fetch(next).await,
+                (None, None) => return None,
+            };
+            let page = match page {
+                Ok(page) => page,
+                Err(e) => return Some(Err(e)),
+            };
+            self.next = next_page(&page);
+            self.items.extend(page.into_data());
+            if let Some(max_items) = self.options.max_items {
+                if self.yielded + self.items.len() >= max_items {
+                    self.next = None;
+                }
+            }
+            if self.options.prefetch {
+                if let Some(next) = self.next.take() {
+                    let task = self.fetch(next);
+                    self.pending = Some(tokio::spawn(task));
+                }
+            }
+        }
+    }
+    /// returns a future that requests a page
+    fn fetch(
+        &self,
+        next: Next,
+    ) -> impl std::future::Future<Output = Result<SuccessList<A, R>, Error>>
+           + Send
+           + 'static {
+        let client = self.client.clone();
+        let (path, query) = match next {
+            Next::Page(number) => {
+                let mut query = self.query.clone();
+                query.push(("page[number]".to_string(), number.to_string()));
+                if let Some(size) = self.options.page_size {
+                    let size = size.min(MAX_PAGE_SIZE);
+                    query.push(("page[size]".to_string(), size.to_string()));
+                }
+                (self.path.clone(), query)
+            }
+            // NOTE: relative links start with the API base path
+            Next::Url(url) if url.starts_with('/') => {
+                (format!("{}{url}", client.get_address()), Vec::new())
+            }
+            Next::Url(url) => (url, Vec::new()),
+        };
+        async move {
+            client
+                .get(path, None, Some(&crate::core::query(&query)))
+                .await
+        }
+    }
+}
+impl<A, R> Drop for Paginator<A, R> {
+    /// cancels the background request when the stream is dropped
+    fn drop(&mut self) {
+        if let Some(pending) = self.pending.take() {
+            pending.abort();
+        }
+    }
+}
+/// returns how the page that follows the given one is requested, if any
+fn next_page<A, R>(page: &SuccessList<A, R>) -> Option<Next> {
+    if let Some(number) =
+        page.get_pagination().and_then(|v| *v.get_next_page())
+    {
+        return Some(Next::Page(number));
+    }
+    page.get_links()
+        .as_ref()
+        .and_then(|v| v.get_next().clone())
+        .map(Next::Url)
+}
+impl Client {
+    /// paginates a list endpoint and returns its resources across all pages.
+    /// The stream ends after the last page, once `max_items` resources were
+    /// yielded, or after the first error.
+    ///
+    /// ## Parameters
+    /// * `path` : path of the list endpoint
+    /// * `query` : query parameters of the list options. Page parameters are
+    ///   replaced by the paginator.
+    /// * `options` : page size, maximum number of resources and prefetching
+    pub fn paginate<A, R>(
+        &self,
+        path: &str,
+        query: &[(String, String)],
+        options: &PaginatorOptions,
+    ) -> impl Stream<Item = Result<jsonapi::Data<A, R>, Error>>
+    where
+        A: DeserializeOwned + Serialize + Debug + Send + 'static,
+        R: DeserializeOwned + Serialize + Debug + Send + 'static,
+    {
+        let query = query
+            .iter()
+            .filter(|(key, _)| !key.starts_with("page["))
+            .cloned()
+            .collect();
+        let state = Paginator {
+            client: self.clone(),
+            path: path.to_string(),
+            query,
+            options: options.clone(),
+            items: VecDeque::new(),
+            next: Some(Next::Page(1)),
+            pending: None,
+            yielded: 0,
+            done: false,
+        };
+        futures::stream::unfold(state, |mut state| async move {
+            if state.done {
+                return None;
+            }
+            let res = state.next().await?;
+            if res.is_err() {
+                state.done = true;
+            }
+            Some((res, state))
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "pagination::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "pagination::tests" --nocapture'
+    use {
+        super::*,
+        crate::testing::{Reply, Server},
+        futures::StreamExt,
+        serde_json::json,
+    };
+    type Page = SuccessList<serde_json::Value, serde_json::Value>;
+    type Item =
+        Result<jsonapi::Data<serde_json::Value, serde_json::Value>, Error>;
+    /// returns a page of workspaces with the given IDs
+    fn page(ids: &[&str], next_page: Option<u32>) -> Reply {
+        let data: Vec<_> = ids
+            .iter()
+            .map(|id| json!({ "id": id, "type": "workspaces" }))
+            .collect();
+        Reply::json(json!({
+            "data": data,
+            "meta": {
+                "pagination": {
+                    "current-page": next_page.map_or(1, |v| v - 1),
+                    "next-page": next_page,
+                    "total-pages": 3,
+                    "total-count": 5
+                }
+            }
+        }))
+    }
+    /// returns IDs of the resources, or `error` for errors
+    fn ids(items: &[Item]) -> Vec<String> {
+        items
+            .iter()
+            .map(|v| match v {
+                Ok(v) => v.id.clone().unwrap_or_default(),
+                Err(_) => "error".to_string(),
+            })
+            .collect()
+    }
+    #[test]
+    fn test_next_page() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs#pagination
+        let page: Page = serde_json::from_value(json!({
+            "data": [],
+            "links": {
+                "self": "https://app.terraform.io/api/v2/organizations/hashicorp/workspaces?page%5Bnumber%5D=1&page%5Bsize%5D=20",
+                "first": "https://app.terraform.io/api/v2/organizations/hashicorp/workspaces?page%5Bnumber%5D=1&page%5Bsize%5D=20",
+                "prev": null,
+                "next": "https://app.terraform.io/api/v2/organizations/hashicorp/workspaces?page%5Bnumber%5D=2&page%5Bsize%5D=20",
+                "last": "https://app.terraform.io/api/v2/organizations/hashicorp/workspaces?page%5Bnumber%5D=2&page%5Bsize%5D=20"
+            },
+            "meta": {
+                "pagination": {
+                    "current-page": 1,
+                    "prev-page": null,
+                    "next-page": 2,
+                    "total-pages": 2,
+                    "total-count": 21
+                }
+            }
+        }))?;
+        assert_eq!(next_page(&page), Some(Next::Page(2)));
+        let page: Page = serde_json::from_value(json!({
+            "data": [],
+            "links": { "next": "https://example.com/api/v2/things?page=2" }
+        }))?;
+        assert_eq!(
+            next_page(&page),
+            Some(Next::Url("https://example.com/api/v2/things?page=2".into()))
+        );
+        let page: Page = serde_json::from_value(json!({
+            "data": [],
+            "meta": {
+                "pagination": {
+                    "current-page": 2,
+                    "prev-page": 1,
+                    "next-page": null,
+                    "total-pages": 2,
+                    "total-count": 21
+                }
+            }
+        }))?;
+        assert_eq!(next_page(&page), None);
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_paginate_max_items() {
+        let server = Server::start(vec![
+            page(&["ws-1", "ws-2"], Some(2)),
+            page(&["ws-3", "ws-4"], Some(3)),
+            page(&["ws-5"], None),
+        ])
+        .await;
+        let options = PaginatorOptions {
+            page_size: Some(500),
+            max_items: Some(3),
+            ..Default::default()
+        };
+        let query = vec![("search[name]".to_string(), "app".to_string())];
+        let stream = server.client().paginate("workspaces", &query, &options);
+        let actual: Vec<Item> = stream.collect().await;
+        assert_eq!(ids(&actual), ["ws-1", "ws-2", "ws-3"]);
+        // NOTE: the third page is not requested as it is past `max_items`
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        assert!(received[1].target.contains("page%5Bnumber%5D=2"));
+        assert!(received[1].target.contains("page%5Bsize%5D=100"));
+        assert!(received[1].target.contains("search%5Bname%5D=app"));
+    }
+    #[tokio::test]
+    async fn test_paginate_prefetch() {
+        let server = Server::start(vec![
+            page(&["ws-1"], Some(2)),
+            page(&["ws-2"], None),
+        ])
+        .await;
+        let options = PaginatorOptions {
+            prefetch: true,
+            ..Default::default()
+        };
+        let stream = server.client().paginate("workspaces", &[], &options);
+        futures::pin_mut!(stream);
+        let first: Item = stream.next().await.unwrap();
+        assert_eq!(first.unwrap().id.as_deref(), Some("ws-1"));
+        // NOTE: the second page is requested before it is needed
+        for _ in 0..100 {
+            if server.received().len() == 2 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(server.received().len(), 2);
+        let rest: Vec<Item> = stream.collect().await;
+        assert_eq!(ids(&rest), ["ws-2"]);
+    }
+    #[tokio::test]
+    async fn test_paginate_ends_after_error() {
+        let server = Server::start(vec![
+            page(&["ws-1", "ws-2"], Some(2)),
+            // NOTE: server errors would be retried
+            Reply::status(400, r#"{"errors":[{"status":"400"}]}"#),
+            page(&["ws-3"], None),
+        ])
+        .await;
+        let options = PaginatorOptions::default();
+        let stream = server.client().paginate("workspaces", &[], &options);
+        let actual: Vec<Item> = stream.collect().await;
+        assert_eq!(ids(&actual), ["ws-1", "ws-2", "error"]);
+        assert_eq!(server.received().len(), 2);
+    }
+    #[tokio::test]
+    async fn test_paginate_rejects_foreign_next_link() {
+        let server = Server::start(vec![Reply::json(json!({
+            "data": [{ "id": "ws-1", "type": "workspaces" }],
+            "links": { "next": "https://attacker.example.org/workspaces" }
+        }))])
+        .await;
+        let options = PaginatorOptions::default();
+        let stream = server.client().paginate("workspaces", &[], &options);
+        let actual: Vec<Item> = stream.collect().await;
+        assert_eq!(ids(&actual), ["ws-1", "error"]);
+        assert!(actual[1].as_ref().unwrap_err().is_invalid_input());
+        assert_eq!(server.received().len(), 1);
+    }
+    #[tokio::test]
+    async fn test_list_all() {
+        let server = Server::start(vec![
+            page(&["ws-1"], Some(2)),
+            page(&["ws-2"], None),
+        ])
+        .await;
+        let client = server.client();
+        let stream = client
+            .workspaces()
+            .list_all(
+                "my-organization",
+                &crate::workspaces::ListOptions::default(),
+                &PaginatorOptions::default(),
+            )
+            .unwrap();
+        let actual: Vec<_> = stream.collect().await;
+        assert_eq!(actual.len(), 2);
+        assert!(server.received()[0]
+            .target
+            .starts_with("/api/v2/organizations/my-organization/workspaces?"));
+        assert!(client
+            .workspaces()
+            .list_all(
+                "../org",
+                &crate::workspaces::ListOptions::default(),
+                &PaginatorOptions::default(),
+            )
+            .is_err());
+    }
+    #[tokio::test]
+    async fn test_drop_aborts_prefetch() {
+        let server = Server::start(vec![]).await;
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        let pending = tokio::spawn(async move {
+            // NOTE: the sender is dropped once the task is aborted
+            let _sender = sender;
+            std::future::pending::<Result<Page, Error>>().await
+        });
+        let paginator = Paginator {
+            client: server.client(),
+            path: "workspaces".to_string(),
+            query: Vec::new(),
+            options: PaginatorOptions::default(),
+            items: VecDeque::new(),
+            next: None,
+            pending: Some(pending),
+            yielded: 0,
+            done: false,
+        };
+        drop(paginator);
+        let actual =
+            tokio::time::timeout(std::time::Duration::from_secs(5), receiver)
+                .await
+                .unwrap();
+        assert!(actual.is_err());
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
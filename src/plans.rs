@@ -0,0 +1,286 @@
+//! This module implements the plans API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/plans).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let logs = client.plans().logs("plan-8F5JFydVYAmtTjET").await.unwrap();
+//!     println!("{}", String::from_utf8_lossy(&logs));
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, Relationship},
+        logs::{self, Source, TailOptions},
+    },
+    bytes::Bytes,
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a plan resource.
+pub type Plan = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single plan.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents possible states of a plan.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Pending,
+    ManagedQueued,
+    Queued,
+    Running,
+    Errored,
+    Canceled,
+    Finished,
+    Unreachable,
+    /// a status that is not known to this library
+    #[serde(other)]
+    Unknown,
+}
+impl Status {
+    /// returns true if the plan can not make any further progress
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Status::Errored
+                | Status::Canceled
+                | Status::Finished
+                | Status::Unreachable
+        )
+    }
+}
+/// This struct represents the time a plan entered each status.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct StatusTimestamps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canceled_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errored_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub managed_queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pending_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<String>,
+}
+/// This struct represents where and how a plan was executed.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct ExecutionDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_pool_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_pool_name: Option<String>,
+}
+/// This struct represents attributes of a plan.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_details: Option<ExecutionDetails>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_changes: Option<bool>,
+    /// pre-signed URL of the raw plan logs, which is valid for one minute
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_read_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_additions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_changes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_destructions: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_imports: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_timestamps: Option<StatusTimestamps>,
+}
+/// This struct represents relationships of a plan.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exports: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_versions: Option<Relationship<Many>>,
+}
+// ────────────────────────────────────────────────────────────
+/// Plans implements the plans API.
+#[derive(Debug, Clone)]
+pub struct Plans<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns plans API
+    pub fn plans(&self) -> Plans<'_> {
+        Plans { client: self }
+    }
+}
+impl<'a> Plans<'a> {
+    /// reads a plan
+    pub async fn read(&self, plan_id: &str) -> Result<Response, Error> {
+        validate_id("plan ID", plan_id)?;
+        self.client
+            .get(format!("plans/{plan_id}"), None, None)
+            .await
+    }
+    /// reads the JSON execution plan of a plan, which is the same document
+    /// `terraform show -json` returns
+    pub async fn read_json_output(
+        &self,
+        plan_id: &str,
+    ) -> Result<serde_json::Value, Error> {
+        validate_id("plan ID", plan_id)?;
+        // NOTE: the endpoint redirects to a pre-signed URL on another host,
+        // which does not receive the authorization header.
+        self.client
+            .get(format!("plans/{plan_id}/json-output"), None, None)
+            .await
+    }
+    /// reads the JSON execution plan of a run's plan
+    pub async fn read_json_output_by_run(
+        &self,
+        run_id: &str,
+    ) -> Result<serde_json::Value, Error> {
+        validate_id("run ID", run_id)?;
+        self.client
+            .get(format!("runs/{run_id}/plan/json-output"), None, None)
+            .await
+    }
+    /// downloads raw logs of a plan
+    pub async fn logs(&self, plan_id: &str) -> Result<Bytes, Error> {
+        let url = self.log_read_url(plan_id).await?;
+        self.client.download(url.as_str(), None).await
+    }
+    /// tails logs of a plan and returns their chunks as they become
+    /// available. The stream ends once the end of the log is reached, and
+    /// after the first error.
+    ///
+    /// ## Parameters
+    /// * `plan_id` : ID of the plan
+    /// * `options` : chunk size, polling intervals and deadline
+    pub fn tail_logs(
+        &self,
+        plan_id: &str,
+        options: &TailOptions,
+    ) -> impl Stream<Item = Result<Bytes, Error>> + 'a {
+        logs::stream(self.client, Source::Plan(plan_id.to_string()), options)
+    }
+    /// returns pre-signed URL of the raw plan logs
+    pub async fn log_read_url(&self, plan_id: &str) -> Result<String, Error> {
+        let plan = self.read(plan_id).await?.into_data();
+        plan.attributes
+            .and_then(|attributes| attributes.log_read_url)
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| Error::Deserialize {
+                path: "data.attributes.log-read-url".to_string(),
+                reason: format!(
+                    "plan `{plan_id}` does not have a log read URL"
+                ),
+            })
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "plans::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "plans::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/plans
+        let input = json!({
+            "data": {
+                "id": "plan-8F5JFydVYAmtTjET",
+                "type": "plans",
+                "attributes": {
+                    "execution-details": { "mode": "remote" },
+                    "generated-configuration": false,
+                    "has-changes": true,
+                    "resource-additions": 0,
+                    "resource-changes": 1,
+                    "resource-destructions": 0,
+                    "resource-imports": 0,
+                    "status": "finished",
+                    "status-timestamps": {
+                        "queued-at": "2018-07-02T22:29:53+00:00",
+                        "pending-at": "2018-07-02T22:29:53+00:00",
+                        "started-at": "2018-07-02T22:29:54+00:00",
+                        "finished-at": "2018-07-02T22:29:58+00:00"
+                    },
+                    "log-read-url": "https://archivist.terraform.io/v1/object/dmF1bHQ6djE6OFA1eEdlSFVHRSs4YUcwaW83a1dRRDA0U2E3T3FiWk1HM2NyQlNtcS9JS1hHN3dmTXJmaFhEYTlHdTF1ZlgxZ2wzVC9kVTlNcjRPOEJkK050VFI3U3dvS2ZuaUhFSGpVenJVUFYzSFVZQ1VZYno3T3UyYjdDRVRPRE5pbWJDVTIrNllQTENyTndYd1Y0ak1DL1dPVlN1VlNxKzYzbWlIcnJPa2dRRkJZZGtFeTNiaU84YlZ4QWs2QzlLY3VJb3lmWlIrajF4a1hYZTlsWnFYemRkL2pNOG9Zc0ZDakdVMlhUWkY3eXZOZWdCYm5WYW1pM1hvQUE3MnNzZWtDMW9VajhmZHhGNjBEeWY4YnY5UUZjNGhMN3JMYTJkTHlCRkx2NTMvODMyZz09"
+                },
+                "relationships": {
+                    "state-versions": { "data": [] }
+                },
+                "links": {
+                    "self": "/api/v2/plans/plan-8F5JFydVYAmtTjET",
+                    "json-output": "/api/v2/plans/plan-8F5JFydVYAmtTjET/json-output"
+                }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.status, Status::Finished);
+        assert!(attributes.status.is_final());
+        assert_eq!(attributes.resource_changes, Some(1));
+        assert_eq!(
+            attributes
+                .execution_details
+                .as_ref()
+                .unwrap()
+                .mode
+                .as_deref(),
+            Some("remote")
+        );
+        assert!(attributes
+            .log_read_url
+            .as_ref()
+            .unwrap()
+            .starts_with("https://archivist.terraform.io/"));
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,397 @@
+//! This module implements the projects API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/projects).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = tfe_oxide::projects::CreateOptions {
+//!         name: "payments".to_string(),
+//!         ..Default::default()
+//!     };
+//!     let project = client
+//!         .projects()
+//!         .create("my-organization", &options)
+//!         .await
+//!         .unwrap()
+//!         .into_data();
+//!     client
+//!         .projects()
+//!         .move_workspaces(
+//!             project.get_id().as_deref().unwrap(),
+//!             &["ws-4j8p6jX1w33MiDC7"],
+//!         )
+//!         .await
+//!         .unwrap();
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, One, Relationship},
+        pagination::PaginatorOptions,
+        workspaces,
+    },
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a project resource.
+pub type Project = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single project.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This struct represents permissions the current API token has on a
+/// project.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Permissions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_create_workspace: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_destroy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_manage_tags: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_manage_teams: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_manage_varsets: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_move_workspace: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_read: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_read_teams: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update: Option<bool>,
+}
+/// This struct represents attributes of a project.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// duration of inactivity after which workspaces of the project are
+    /// destroyed, e.g `14d`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_destroy_activity_duration: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_count: Option<u32>,
+}
+/// This struct represents relationships of a project.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing projects of an organization.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of projects per page
+    pub page_size: Option<u32>,
+    /// search by name
+    pub query: Option<String>,
+    /// only return projects with the given names
+    pub names: Vec<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut res = crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+            ("q", self.query.clone()),
+        ]);
+        if !self.names.is_empty() {
+            res.push(("filter[names]".to_string(), self.names.join(",")));
+        }
+        res
+    }
+}
+/// This struct contains options for creating a project.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    /// name of the project, which must be unique within the organization
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_destroy_activity_duration: Option<String>,
+}
+/// This struct contains options for updating a project. Unset fields are
+/// left unchanged.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateOptions {
+    /// new name of the project
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_destroy_activity_duration: Option<String>,
+}
+// ────────────────────────────────────────────────────────────
+/// Projects implements the projects API.
+#[derive(Debug, Clone)]
+pub struct Projects<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns projects API
+    pub fn projects(&self) -> Projects<'_> {
+        Projects { client: self }
+    }
+}
+impl<'a> Projects<'a> {
+    /// lists projects of an organization
+    pub async fn list(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Project>, Error> {
+        validate_id("organization", organization)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                format!("organizations/{organization}/projects"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns projects of an organization across all pages. The stream ends
+    /// after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<Project, Error>>, Error> {
+        validate_id("organization", organization)?;
+        Ok(self.client.paginate(
+            &format!("organizations/{organization}/projects"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// creates a project in an organization
+    pub async fn create(
+        &self,
+        organization: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        if options.name.is_empty() {
+            return Err(Error::InvalidInput {
+                reason: "project name is required".to_string(),
+            });
+        }
+        let payload = jsonapi::Request::builder("projects")
+            .attributes(options)
+            .build();
+        self.client
+            .post(
+                format!("organizations/{organization}/projects"),
+                None,
+                payload,
+            )
+            .await
+    }
+    /// reads a project
+    pub async fn read(&self, project_id: &str) -> Result<Response, Error> {
+        validate_id("project ID", project_id)?;
+        self.client
+            .get(format!("projects/{project_id}"), None, None)
+            .await
+    }
+    /// updates a project
+    pub async fn update(
+        &self,
+        project_id: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("project ID", project_id)?;
+        let payload = jsonapi::Request::builder("projects")
+            .attributes(options)
+            .build();
+        self.client
+            .patch(format!("projects/{project_id}"), None, payload)
+            .await
+    }
+    /// deletes a project. Only empty projects can be deleted.
+    pub async fn delete(&self, project_id: &str) -> Result<(), Error> {
+        validate_id("project ID", project_id)?;
+        self.client
+            .delete(format!("projects/{project_id}"), None, ())
+            .await
+    }
+    /// moves workspaces to a project, one by one
+    ///
+    /// NOTE: the API has no bulk endpoint, so workspaces that were moved
+    /// before a failure stay in the new project.
+    pub async fn move_workspaces(
+        &self,
+        project_id: &str,
+        workspace_ids: &[&str],
+    ) -> Result<Vec<workspaces::Workspace>, Error> {
+        validate_id("project ID", project_id)?;
+        let options = workspaces::UpdateOptions {
+            project_id: Some(project_id.to_string()),
+            ..Default::default()
+        };
+        let mut res = Vec::with_capacity(workspace_ids.len());
+        for workspace_id in workspace_ids {
+            let response = self
+                .client
+                .workspaces()
+                .update_by_id(workspace_id, &options)
+                .await?;
+            res.push(response.into_data());
+        }
+        Ok(res)
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "projects::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "projects::tests" --nocapture'
+    use {
+        super::*,
+        crate::testing::{Reply, Server},
+        serde_json::json,
+    };
+    /// returns a workspace document with the given ID
+    fn workspace(id: &str) -> serde_json::Value {
+        json!({
+            "data": {
+                "id": id,
+                "type": "workspaces",
+                "attributes": { "name": id }
+            }
+        })
+    }
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/projects
+        let input = json!({
+            "data": {
+                "id": "prj-WsVcWRr7SSxRqvGA",
+                "type": "projects",
+                "attributes": {
+                    "name": "Infrastructure Project",
+                    "description": "Hosts our infrastructure workspaces",
+                    "created-at": "2023-09-11T21:40:28.493Z",
+                    "workspace-count": 4,
+                    "team-count": 1,
+                    "permissions": {
+                        "can-update": true,
+                        "can-destroy": true,
+                        "can-create-workspace": true
+                    },
+                    "auto-destroy-activity-duration": "2d"
+                },
+                "relationships": {
+                    "organization": {
+                        "data": { "id": "my-organization", "type": "organizations" }
+                    }
+                },
+                "links": { "self": "/api/v2/projects/prj-WsVcWRr7SSxRqvGA" }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.name, "Infrastructure Project");
+        assert_eq!(attributes.workspace_count, Some(4));
+        assert_eq!(
+            attributes.auto_destroy_activity_duration.as_deref(),
+            Some("2d")
+        );
+        Ok(())
+    }
+    #[tokio::test]
+    async fn test_move_workspaces() {
+        let server = Server::start(vec![
+            Reply::json(workspace("ws-1")),
+            Reply::json(workspace("ws-2")),
+        ])
+        .await;
+        let client = server.client();
+        let actual = client
+            .projects()
+            .move_workspaces("prj-123", &["ws-1", "ws-2"])
+            .await
+            .unwrap();
+        let ids: Vec<_> = actual.iter().map(|v| v.id.as_deref()).collect();
+        assert_eq!(ids, vec![Some("ws-1"), Some("ws-2")]);
+        let received = server.received();
+        assert_eq!(received.len(), 2);
+        for (request, id) in received.iter().zip(["ws-1", "ws-2"]) {
+            assert_eq!(request.method, "PATCH");
+            assert_eq!(request.target, format!("/api/v2/workspaces/{id}"));
+            let body: serde_json::Value =
+                serde_json::from_slice(&request.body).unwrap();
+            assert_eq!(
+                body["data"]["relationships"]["project"],
+                json!({ "data": { "type": "projects", "id": "prj-123" } })
+            );
+        }
+    }
+    #[tokio::test]
+    async fn test_move_workspaces_stops_at_first_error() {
+        let server = Server::start(vec![
+            Reply::json(workspace("ws-1")),
+            Reply::status(404, ""),
+            Reply::json(workspace("ws-3")),
+        ])
+        .await;
+        let client = server.client();
+        let actual = client
+            .projects()
+            .move_workspaces("prj-123", &["ws-1", "ws-2", "ws-3"])
+            .await
+            .unwrap_err();
+        assert!(actual.is_not_found());
+        let targets: Vec<_> =
+            server.received().iter().map(|v| v.target.clone()).collect();
+        assert_eq!(
+            targets,
+            vec!["/api/v2/workspaces/ws-1", "/api/v2/workspaces/ws-2"]
+        );
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
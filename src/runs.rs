@@ -0,0 +1,1065 @@
+//! This module implements the runs API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/run).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = tfe_oxide::runs::CreateOptions {
+//!         workspace_id: "ws-4j8p6jX1w33MiDC7".to_string(),
+//!         message: Some("triggered from rust".to_string()),
+//!         ..Default::default()
+//!     };
+//!     let run = client.runs().create(&options).await.unwrap();
+//!     println!("{:?}", run.get_data().get_id());
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, One, Relationship},
+        pagination::PaginatorOptions,
+    },
+    futures::{Stream, StreamExt},
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+    std::{fmt::Display, time::Duration},
+    tokio::time::Instant,
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a run resource.
+pub type Run = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single run.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents possible states of a run.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    #[default]
+    Pending,
+    Fetching,
+    FetchingCompleted,
+    PrePlanRunning,
+    PrePlanCompleted,
+    Queuing,
+    PlanQueued,
+    Planning,
+    Planned,
+    CostEstimating,
+    CostEstimated,
+    PolicyChecking,
+    PolicyOverride,
+    PolicySoftFailed,
+    PolicyChecked,
+    Confirmed,
+    PostPlanRunning,
+    PostPlanCompleted,
+    PostPlanAwaitingDecision,
+    PlannedAndFinished,
+    PlannedAndSaved,
+    ApplyQueued,
+    QueuingApply,
+    PreApplyRunning,
+    PreApplyCompleted,
+    Applying,
+    Applied,
+    Discarded,
+    Errored,
+    Canceled,
+    ForceCanceled,
+    /// a status that is not known to this library
+    #[serde(other)]
+    Unknown,
+}
+impl Status {
+    /// returns true if the run can not make any further progress
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            Status::Applied
+                | Status::PlannedAndFinished
+                | Status::PlannedAndSaved
+                | Status::Discarded
+                | Status::Errored
+                | Status::Canceled
+                | Status::ForceCanceled
+        )
+    }
+    /// returns true if the run is waiting for a user to confirm, discard or
+    /// override it
+    pub fn needs_decision(&self) -> bool {
+        matches!(
+            self,
+            Status::Planned
+                | Status::CostEstimated
+                | Status::PolicyChecked
+                | Status::PolicyOverride
+                | Status::PolicySoftFailed
+                | Status::PostPlanAwaitingDecision
+        )
+    }
+}
+impl Display for Status {
+    /// prints the status the way the API represents it, e.g `plan_queued`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = serde_json::to_value(self).unwrap_or_default();
+        write!(f, "{}", value.as_str().unwrap_or("unknown"))
+    }
+}
+/// This struct represents the time a run entered each status.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct StatusTimestamps {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applied_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub applying_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apply_queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canceled_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_estimated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_estimating_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub discarded_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errored_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetching_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force_canceled_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_queueable_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_queued_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planned_and_finished_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planned_and_saved_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planned_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planning_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_checked_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_soft_failed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_plan_completed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_plan_running_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_apply_completed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_apply_running_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_plan_completed_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pre_plan_running_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queuing_at: Option<String>,
+}
+/// This struct represents actions that can be performed on a run.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Actions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_cancelable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_confirmable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_discardable: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_force_cancelable: Option<bool>,
+}
+/// This struct represents permissions the current API token has on a run.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Permissions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_cancel: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_comment: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_discard: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_force_cancel: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_force_execute: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_override_policy: Option<bool>,
+}
+/// This struct represents a Terraform input variable that is set for a
+/// single run. `value` must be an HCL expression, e.g `"\"foo\""`.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+pub struct Variable {
+    pub key: String,
+    pub value: String,
+}
+/// This struct represents attributes of a run.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Actions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_config_generation: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_empty_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub canceled_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub has_changes: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_destroy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub position_in_queue: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replace_addrs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save_plan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_timestamps: Option<StatusTimestamps>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_addrs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<Vec<Variable>>,
+}
+/// This struct represents relationships of a run.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apply: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comments: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration_version: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmed_by: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost_estimate: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_checks: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_events: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub task_stages: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing runs.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of runs per page
+    pub page_size: Option<u32>,
+    /// comma-separated list of operations, e.g `plan_only,plan_and_apply`
+    pub operation: Option<String>,
+    /// comma-separated list of run statuses
+    pub status: Option<String>,
+    /// comma-separated list of run sources, e.g `tfe-api,tfe-ui`
+    pub source: Option<String>,
+    /// search by VCS username of the run creator
+    pub user: Option<String>,
+    /// search by commit SHA that triggered the run
+    pub commit: Option<String>,
+    /// search by run ID, message, commit SHA or VCS username
+    pub basic: Option<String>,
+    /// related resources to include, e.g `plan`
+    pub include: Vec<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut res = crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+            ("filter[operation]", self.operation.clone()),
+            ("filter[status]", self.status.clone()),
+            ("filter[source]", self.source.clone()),
+            ("search[user]", self.user.clone()),
+            ("search[commit]", self.commit.clone()),
+            ("search[basic]", self.basic.clone()),
+        ]);
+        if !self.include.is_empty() {
+            res.push(("include".to_string(), self.include.join(",")));
+        }
+        res
+    }
+}
+/// This struct contains options for reading a run.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// related resources to include, e.g `plan`
+    pub include: Vec<String>,
+}
+impl ReadOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        if self.include.is_empty() {
+            return Vec::new();
+        }
+        vec![("include".to_string(), self.include.join(","))]
+    }
+}
+/// This struct contains options for creating a run.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    /// ID of the workspace to create the run in
+    #[serde(skip)]
+    pub workspace_id: String,
+    /// ID of the configuration version to use. The latest configuration
+    /// version of the workspace is used when unset.
+    #[serde(skip)]
+    pub configuration_version_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_config_generation: Option<bool>,
+    /// allows applying runs that do not change any resources
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_empty_apply: Option<bool>,
+    /// overrides auto-apply setting of the workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub debugging_mode: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_destroy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// creates a speculative run that can not be applied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_only: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replace_addrs: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub save_plan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_addrs: Option<Vec<String>>,
+    /// Terraform version of a plan-only run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<Vec<Variable>>,
+}
+/// This struct contains options for run actions, e.g apply or discard.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+pub struct ActionOptions {
+    /// comment to add to the run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+/// This struct contains options for waiting on a run to reach a status.
+///
+/// The run is polled with an exponential backoff that uses an exponent base
+/// of 2, starting at `interval` and capped at `max_interval`, until
+/// `deadline`. Default values are taken from the retry intervals and the
+/// wait timeout of `crate::core::ReqwestClientBuilder`, and
+/// `WaitOptions::from(client.get_http_settings())` reuses the settings of an
+/// existing client.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub struct WaitOptions {
+    /// waiting time between the first two polls
+    pub interval: Duration,
+    /// maximum waiting time between two polls
+    pub max_interval: Duration,
+    /// maximum total waiting time. Waits indefinitely when it is explicitly
+    /// unset.
+    pub deadline: Option<Duration>,
+}
+impl WaitOptions {
+    /// returns waiting time of the poll that comes after one that waited
+    /// for `current`
+    pub(crate) fn next_interval(&self, current: Duration) -> Duration {
+        current
+            .saturating_mul(2)
+            .max(self.interval)
+            .min(self.max_interval)
+    }
+}
+impl Default for WaitOptions {
+    fn default() -> Self {
+        WaitOptions::from(&crate::core::ReqwestClientBuilder::default())
+    }
+}
+impl From<&crate::core::ReqwestClientBuilder> for WaitOptions {
+    /// uses retry intervals of the HTTP client as polling intervals and its
+    /// wait timeout as deadline
+    fn from(arg: &crate::core::ReqwestClientBuilder) -> Self {
+        WaitOptions {
+            interval: Duration::from_millis(arg.get_min_retry_interval()),
+            max_interval: Duration::from_millis(arg.get_max_retry_interval()),
+            deadline: Some(Duration::from_millis(arg.get_wait_timeout())),
+        }
+    }
+}
+/// This struct paces polls according to `WaitOptions` and enforces their
+/// deadline. It is shared by pollers, e.g run watchers and log tails.
+#[derive(Debug, Clone)]
+pub(crate) struct Backoff {
+    options: WaitOptions,
+    /// waiting time before the next poll, which is sent right away when it
+    /// is `None`
+    wait: Option<Duration>,
+    started: Instant,
+}
+impl Backoff {
+    /// returns a backoff whose first poll is sent right away
+    pub(crate) fn new(options: WaitOptions) -> Self {
+        Backoff {
+            options,
+            wait: None,
+            started: Instant::now(),
+        }
+    }
+    /// waits before the next poll. Returns the elapsed time as an error
+    /// once the deadline is exceeded.
+    pub(crate) async fn wait(&self) -> Result<(), Duration> {
+        let Some(wait) = self.wait else {
+            return Ok(());
+        };
+        let wait = match self.options.deadline {
+            Some(deadline) => {
+                let elapsed = self.started.elapsed();
+                if elapsed >= deadline {
+                    return Err(elapsed);
+                }
+                wait.min(deadline - elapsed)
+            }
+            None => wait,
+        };
+        tokio::time::sleep(wait).await;
+        Ok(())
+    }
+    /// increases the waiting time before the next poll
+    pub(crate) fn increase(&mut self) {
+        self.wait = Some(match self.wait {
+            Some(wait) => self.options.next_interval(wait),
+            None => self.options.interval,
+        });
+    }
+    /// resets the waiting time, so that the next poll is sent right away
+    pub(crate) fn reset(&mut self) {
+        self.wait = None;
+    }
+}
+/// This struct holds the state of a run watcher between two polls.
+struct Watch<'a> {
+    client: &'a Client,
+    run_id: String,
+    targets: Vec<Status>,
+    /// status of the last run that was returned
+    last: Option<Status>,
+    backoff: Backoff,
+    done: bool,
+}
+impl<'a> Watch<'a> {
+    /// polls the run until its status changes and returns it
+    async fn next(&mut self) -> Result<Run, Error> {
+        loop {
+            if let Err(elapsed) = self.backoff.wait().await {
+                return Err(Error::Timeout {
+                    reason: format!(
+                        "timed out after {}ms waiting for run `{}` to reach one of {:?}",
+                        elapsed.as_millis(),
+                        self.run_id,
+                        self.targets
+                    ),
+                });
+            }
+            self.backoff.increase();
+            let run = self
+                .client
+                .runs()
+                .read(self.run_id.as_str(), &ReadOptions::default())
+                .await?
+                .into_data();
+            let status = run
+                .attributes
+                .as_ref()
+                .map(|attributes| attributes.status)
+                .unwrap_or_default();
+            if self.last == Some(status) {
+                continue;
+            }
+            tracing::debug!("\nrun `{}` is {}", self.run_id, status);
+            self.last = Some(status);
+            self.done = status.is_final() || self.targets.contains(&status);
+            return Ok(run);
+        }
+    }
+}
+// ────────────────────────────────────────────────────────────
+/// returns request document for creating a run
+fn create_request(
+    options: &CreateOptions,
+) -> jsonapi::Request<&CreateOptions> {
+    let mut request = jsonapi::Request::builder("runs")
+        .attributes(options)
+        .to_one("workspace", "workspaces", Some(&options.workspace_id));
+    if let Some(id) = &options.configuration_version_id {
+        request = request.to_one(
+            "configuration-version",
+            "configuration-versions",
+            Some(id),
+        );
+    }
+    request.build()
+}
+// ────────────────────────────────────────────────────────────
+/// Runs implements the runs API.
+#[derive(Debug, Clone)]
+pub struct Runs<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns runs API
+    pub fn runs(&self) -> Runs<'_> {
+        Runs { client: self }
+    }
+}
+impl<'a> Runs<'a> {
+    /// lists runs of a workspace
+    pub async fn list(
+        &self,
+        workspace_id: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Run>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                format!("workspaces/{workspace_id}/runs"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns runs of a workspace across all pages. The stream ends after the
+    /// last page or after the first error.
+    pub fn list_all(
+        &self,
+        workspace_id: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<Run, Error>>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        Ok(self.client.paginate(
+            &format!("workspaces/{workspace_id}/runs"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// lists runs of all workspaces of an organization
+    pub async fn list_for_organization(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Run>, Error> {
+        validate_id("organization", organization)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                format!("organizations/{organization}/runs"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// creates a run
+    pub async fn create(
+        &self,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", options.workspace_id.as_str())?;
+        if let Some(id) = &options.configuration_version_id {
+            validate_id("configuration version ID", id)?;
+        }
+        self.client
+            .post("runs", None, create_request(options))
+            .await
+    }
+    /// reads a run
+    pub async fn read(
+        &self,
+        run_id: &str,
+        options: &ReadOptions,
+    ) -> Result<Response, Error> {
+        validate_id("run ID", run_id)?;
+        let query = options.to_query();
+        self.client
+            .get(
+                format!("runs/{run_id}"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await
+    }
+    /// applies a run that is waiting for confirmation
+    pub async fn apply(
+        &self,
+        run_id: &str,
+        options: &ActionOptions,
+    ) -> Result<(), Error> {
+        self.action(run_id, "apply", options).await
+    }
+    /// skips any remaining work of a run that is waiting for confirmation or
+    /// priority
+    pub async fn discard(
+        &self,
+        run_id: &str,
+        options: &ActionOptions,
+    ) -> Result<(), Error> {
+        self.action(run_id, "discard", options).await
+    }
+    /// interrupts a run that is currently planning or applying
+    pub async fn cancel(
+        &self,
+        run_id: &str,
+        options: &ActionOptions,
+    ) -> Result<(), Error> {
+        self.action(run_id, "cancel", options).await
+    }
+    /// ends a run immediately. It is only allowed after a cancel request was
+    /// made and the run's `is-force-cancelable` action is true.
+    pub async fn force_cancel(
+        &self,
+        run_id: &str,
+        options: &ActionOptions,
+    ) -> Result<(), Error> {
+        self.action(run_id, "force-cancel", options).await
+    }
+    /// discards all runs that are ahead of a pending run and starts it
+    pub async fn force_execute(&self, run_id: &str) -> Result<(), Error> {
+        self.action(run_id, "force-execute", &()).await
+    }
+    /// returns a stream that polls a run and yields it every time its
+    /// status changes, starting with its current status.
+    ///
+    /// The stream ends after yielding a run whose status is one of `targets`
+    /// or a final status, e.g `errored`. It yields an error and ends if a
+    /// request fails or `options.deadline` is exceeded.
+    pub fn watch(
+        &self,
+        run_id: &str,
+        targets: &[Status],
+        options: &WaitOptions,
+    ) -> impl Stream<Item = Result<Run, Error>> + 'a {
+        let state = Watch {
+            client: self.client,
+            run_id: run_id.to_string(),
+            targets: targets.to_vec(),
+            last: None,
+            backoff: Backoff::new(options.clone()),
+            done: false,
+        };
+        futures::stream::unfold(state, |mut state| async move {
+            if state.done {
+                return None;
+            }
+            let res = state.next().await;
+            if res.is_err() {
+                state.done = true;
+            }
+            Some((res, state))
+        })
+    }
+    /// polls a run until its status is one of `targets` or a final status,
+    /// e.g `errored`, and returns it.
+    ///
+    /// ## Parameters
+    /// * `run_id` : ID of the run to wait for
+    /// * `targets` : statuses to wait for, e.g `planned` and `applied`
+    /// * `options` : polling intervals and deadline
+    /// * `on_transition` : called with the run every time its status changes
+    pub async fn wait_for_run<F>(
+        &self,
+        run_id: &str,
+        targets: &[Status],
+        options: &WaitOptions,
+        mut on_transition: F,
+    ) -> Result<Run, Error>
+    where
+        F: FnMut(&Run),
+    {
+        let stream = self.watch(run_id, targets, options);
+        futures::pin_mut!(stream);
+        let mut last: Option<Run> = None;
+        while let Some(run) = stream.next().await {
+            let run = run?;
+            on_transition(&run);
+            last = Some(run);
+        }
+        last.ok_or_else(|| Error::InvalidInput {
+            reason: format!("run `{run_id}` was not polled"),
+        })
+    }
+    /// sends a request to an action endpoint of a run
+    async fn action<T>(
+        &self,
+        run_id: &str,
+        action: &str,
+        payload: &T,
+    ) -> Result<(), Error>
+    where
+        T: Serialize + std::fmt::Debug + Send + Sync,
+    {
+        validate_id("run ID", run_id)?;
+        self.client
+            .post(format!("runs/{run_id}/actions/{action}"), None, payload)
+            .await
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "runs::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "runs::tests" --nocapture'
+    use {
+        super::*,
+        crate::testing::{Reply, Server},
+        serde_json::json,
+    };
+    /// returns a response that reads a run with the given status
+    fn run(status: &str) -> Reply {
+        Reply::json(json!({
+            "data": {
+                "id": "run-123",
+                "type": "runs",
+                "attributes": { "status": status }
+            }
+        }))
+    }
+    /// returns options that poll quickly
+    fn options(deadline: Duration) -> WaitOptions {
+        WaitOptions {
+            interval: Duration::from_millis(1),
+            max_interval: Duration::from_millis(5),
+            deadline: Some(deadline),
+        }
+    }
+    /// returns statuses of the given runs
+    fn statuses(runs: &[Result<Run, Error>]) -> Vec<Option<Status>> {
+        runs.iter()
+            .map(|run| {
+                run.as_ref()
+                    .ok()
+                    .and_then(|run| run.attributes.as_ref())
+                    .map(|attributes| attributes.status)
+            })
+            .collect()
+    }
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/run
+        let input = json!({
+            "data": {
+                "id": "run-CZcmD7eagjhyX0vN",
+                "type": "runs",
+                "attributes": {
+                    "actions": {
+                        "is-cancelable": true,
+                        "is-confirmable": false,
+                        "is-discardable": false,
+                        "is-force-cancelable": false
+                    },
+                    "allow-empty-apply": false,
+                    "auto-apply": false,
+                    "has-changes": false,
+                    "is-destroy": false,
+                    "message": "Custom message",
+                    "plan-only": false,
+                    "source": "tfe-api",
+                    "status": "plan_queued",
+                    "status-timestamps": {
+                        "plan-queueable-at": "2021-05-24T07:38:04+00:00"
+                    },
+                    "target-addrs": ["example.resource_address"],
+                    "variables": [{ "key": "foo", "value": "\"bar\"" }],
+                    "permissions": { "can-apply": true }
+                },
+                "relationships": {
+                    "workspace": {
+                        "data": { "id": "ws-4j8p6jX1w33MiDC7", "type": "workspaces" }
+                    }
+                }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.status, Status::PlanQueued);
+        assert_eq!(attributes.status.to_string(), "plan_queued");
+        assert_eq!(
+            attributes
+                .status_timestamps
+                .as_ref()
+                .unwrap()
+                .plan_queueable_at
+                .as_deref(),
+            Some("2021-05-24T07:38:04+00:00")
+        );
+        assert_eq!(attributes.variables.as_ref().unwrap()[0].value, "\"bar\"");
+        Ok(())
+    }
+    #[test]
+    fn test_unknown_status() -> Result<(), serde_json::Error> {
+        let actual: Status = serde_json::from_value(json!("not_a_status"))?;
+        assert_eq!(actual, Status::Unknown);
+        assert!(Status::Applied.is_final());
+        assert!(!Status::Planning.is_final());
+        assert!(Status::PolicyChecked.needs_decision());
+        Ok(())
+    }
+    #[test]
+    fn test_wait_options() {
+        let builder = crate::core::ReqwestClientBuilder::new()
+            .set_min_retry_interval(500)
+            .unwrap()
+            .set_max_retry_interval(3000)
+            .unwrap()
+            .set_wait_timeout(60 * 1000)
+            .unwrap();
+        let options = WaitOptions::from(&builder);
+        assert_eq!(options.interval, Duration::from_millis(500));
+        assert_eq!(options.max_interval, Duration::from_millis(3000));
+        assert_eq!(options.deadline, Some(Duration::from_secs(60)));
+        assert_eq!(
+            WaitOptions::default().deadline,
+            Some(Duration::from_secs(60 * 60))
+        );
+        let intervals: Vec<u128> = (0..4)
+            .scan(options.interval, |current, _| {
+                *current = options.next_interval(*current);
+                Some(current.as_millis())
+            })
+            .collect();
+        assert_eq!(intervals, vec![1000, 2000, 3000, 3000]);
+    }
+    #[tokio::test]
+    async fn test_watch_skips_repeated_statuses() {
+        let server = Server::start(vec![
+            run("pending"),
+            run("pending"),
+            run("planning"),
+            run("planning"),
+            run("planned"),
+            run("applying"),
+        ])
+        .await;
+        let client = server.client();
+        let actual: Vec<Result<Run, Error>> = client
+            .runs()
+            .watch("run-123", &[Status::Planned], &options(Duration::MAX))
+            .collect()
+            .await;
+        assert_eq!(
+            statuses(&actual),
+            vec![
+                Some(Status::Pending),
+                Some(Status::Planning),
+                Some(Status::Planned)
+            ]
+        );
+        // NOTE: the stream ends at the target status
+        let received = server.received();
+        assert_eq!(received.len(), 5);
+        assert!(received
+            .iter()
+            .all(|request| request.target == "/api/v2/runs/run-123"));
+    }
+    #[tokio::test]
+    async fn test_watch_stops_at_final_status() {
+        let server = Server::start(vec![
+            run("planning"),
+            run("errored"),
+            run("applied"),
+        ])
+        .await;
+        let client = server.client();
+        let actual: Vec<Result<Run, Error>> = client
+            .runs()
+            .watch("run-123", &[Status::Applied], &options(Duration::MAX))
+            .collect()
+            .await;
+        assert_eq!(
+            statuses(&actual),
+            vec![Some(Status::Planning), Some(Status::Errored)]
+        );
+        assert_eq!(server.received().len(), 2);
+    }
+    #[tokio::test]
+    async fn test_watch_deadline() {
+        let server = Server::start(vec![run("pending"); 100]).await;
+        let client = server.client();
+        let options = WaitOptions {
+            interval: Duration::from_millis(10),
+            max_interval: Duration::from_millis(10),
+            deadline: Some(Duration::from_millis(50)),
+        };
+        let actual: Vec<Result<Run, Error>> = client
+            .runs()
+            .watch("run-123", &[Status::Applied], &options)
+            .collect()
+            .await;
+        // NOTE: the stream ends after the timeout error
+        assert_eq!(actual.len(), 2);
+        assert_eq!(statuses(&actual[..1]), vec![Some(Status::Pending)]);
+        assert!(matches!(actual[1], Err(Error::Timeout { .. })));
+    }
+    #[tokio::test]
+    async fn test_wait_for_run() {
+        let server = Server::start(vec![
+            run("pending"),
+            run("plan_queued"),
+            run("plan_queued"),
+            run("planning"),
+            run("planned"),
+        ])
+        .await;
+        let client = server.client();
+        let mut transitions = Vec::new();
+        let actual = client
+            .runs()
+            .wait_for_run(
+                "run-123",
+                &[Status::Planned, Status::Applied],
+                &options(Duration::MAX),
+                |run| {
+                    transitions.push(run.attributes.as_ref().unwrap().status);
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(actual.attributes.unwrap().status, Status::Planned);
+        assert_eq!(
+            transitions,
+            vec![
+                Status::Pending,
+                Status::PlanQueued,
+                Status::Planning,
+                Status::Planned
+            ]
+        );
+        // NOTE: errors end the wait, after the transitions seen so far
+        let server =
+            Server::start(vec![run("pending"), Reply::status(404, "")]).await;
+        let client = server.client();
+        let mut transitions = Vec::new();
+        let actual = client
+            .runs()
+            .wait_for_run(
+                "run-123",
+                &[Status::Applied],
+                &options(Duration::MAX),
+                |run| {
+                    transitions.push(run.attributes.as_ref().unwrap().status);
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(actual.is_not_found());
+        assert_eq!(transitions, vec![Status::Pending]);
+    }
+    #[test]
+    fn test_create_request() {
+        let options = CreateOptions {
+            workspace_id: "ws-123".to_string(),
+            configuration_version_id: Some("cv-123".to_string()),
+            plan_only: Some(true),
+            ..Default::default()
+        };
+        let expected = json!({
+            "data": {
+                "type": "runs",
+                "attributes": { "plan-only": true },
+                "relationships": {
+                    "workspace": {
+                        "data": { "type": "workspaces", "id": "ws-123" }
+                    },
+                    "configuration-version": {
+                        "data": { "type": "configuration-versions", "id": "cv-123" }
+                    }
+                }
+            }
+        });
+        assert_eq!(
+            serde_json::to_value(create_request(&options)).unwrap(),
+            expected
+        );
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,548 @@
+//! This module implements Terraform state format version 4, which is used by
+//! Terraform 0.12 and later.
+//!
+//! Values of sensitive outputs are kept in `crate::core::Secret`, so they
+//! are wiped from memory when the state is dropped and are never printed.
+//! Resource instance attributes listed in `sensitive_attributes` are kept as
+//! plain values, but `Debug` output of instances redacts them.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let raw = client
+//!         .state_versions()
+//!         .download("sv-SDboVZC8TCxXEneJ")
+//!         .await
+//!         .unwrap();
+//!     let state = tfe_oxide::state::State::from_slice(&raw).unwrap();
+//!     for address in state.addresses() {
+//!         println!("{address}");
+//!     }
+//! }
+//! ```
+use {
+    crate::core::{Error, Secret},
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+    std::collections::BTreeMap,
+};
+/// state format version this module implements
+pub const VERSION: u64 = 4;
+// ────────────────────────────────────────────────────────────
+/// This struct represents a Terraform state.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+pub struct State {
+    pub version: u64,
+    pub terraform_version: String,
+    pub serial: u64,
+    pub lineage: String,
+    #[serde(default)]
+    pub outputs: BTreeMap<String, Output>,
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_results: Option<serde_json::Value>,
+}
+impl State {
+    /// parses a raw state file, e.g `terraform.tfstate`, returning an error
+    /// if it is not valid JSON or uses a different format version.
+    pub fn from_slice(raw: &[u8]) -> Result<Self, Error> {
+        let state: State =
+            serde_json::from_slice(raw).map_err(|e| Error::InvalidInput {
+                reason: format!("state is not valid: {e}"),
+            })?;
+        if state.version != VERSION {
+            return Err(Error::InvalidInput {
+                reason: format!(
+                    "unsupported state format version {}, expected {VERSION}",
+                    state.version
+                ),
+            });
+        }
+        Ok(state)
+    }
+    /// returns addresses of all resource instances, e.g
+    /// `module.network.aws_subnet.private[0]`. Deposed instances are left
+    /// out.
+    pub fn addresses(&self) -> Vec<String> {
+        self.resources
+            .iter()
+            .flat_map(|resource| {
+                resource
+                    .instances
+                    .iter()
+                    .filter(|instance| instance.deposed.is_none())
+                    .map(move |instance| resource.instance_address(instance))
+            })
+            .collect()
+    }
+    /// returns the resource instance that has the given address, e.g
+    /// `aws_instance.web["blue"]`. Deposed instances are left out.
+    pub fn lookup(&self, address: &str) -> Option<(&Resource, &Instance)> {
+        self.resources.iter().find_map(|resource| {
+            resource
+                .instances
+                .iter()
+                .filter(|instance| instance.deposed.is_none())
+                .find(|instance| {
+                    resource.instance_address(instance) == address
+                })
+                .map(|instance| (resource, instance))
+        })
+    }
+    /// returns the root module output that has the given name
+    pub fn output(&self, name: &str) -> Option<&Output> {
+        self.outputs.get(name)
+    }
+}
+// ────────────────────────────────────────────────────────────
+/// This enum represents the value of an output.
+#[derive(PartialEq, Debug, Clone)]
+pub enum OutputValue {
+    Plain(serde_json::Value),
+    /// JSON-encoded value of a sensitive output
+    Sensitive(Secret),
+}
+/// This struct represents a root module output.
+#[derive(PartialEq, Debug, Clone, Deserialize, Serialize)]
+#[serde(from = "RawOutput", into = "RawOutput")]
+pub struct Output {
+    pub value: OutputValue,
+    /// type of the value in Terraform's JSON type notation
+    pub kind: Option<serde_json::Value>,
+}
+impl Output {
+    /// returns true if the output is sensitive
+    pub fn is_sensitive(&self) -> bool {
+        matches!(self.value, OutputValue::Sensitive(_))
+    }
+    /// returns the value of a non-sensitive output, or `None` if the output
+    /// is sensitive
+    pub fn get_value(&self) -> Option<&serde_json::Value> {
+        match &self.value {
+            OutputValue::Plain(value) => Some(value),
+            OutputValue::Sensitive(_) => None,
+        }
+    }
+    /// returns the value of the output, even if it is sensitive.
+    ///
+    /// SECURITY: the returned value is not wiped from memory when dropped.
+    pub fn expose_value(&self) -> serde_json::Value {
+        match &self.value {
+            OutputValue::Plain(value) => value.clone(),
+            // NOTE: the secret always holds a serialized JSON value
+            OutputValue::Sensitive(secret) => {
+                serde_json::from_str(secret.expose()).unwrap_or_default()
+            }
+        }
+    }
+}
+/// This struct represents an output the way it is serialized.
+#[derive(Deserialize, Serialize)]
+struct RawOutput {
+    value: serde_json::Value,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    kind: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    sensitive: bool,
+}
+impl From<RawOutput> for Output {
+    fn from(arg: RawOutput) -> Self {
+        let value = match arg.sensitive {
+            true => OutputValue::Sensitive(Secret::new(arg.value.to_string())),
+            false => OutputValue::Plain(arg.value),
+        };
+        Output {
+            value,
+            kind: arg.kind,
+        }
+    }
+}
+impl From<Output> for RawOutput {
+    fn from(arg: Output) -> Self {
+        RawOutput {
+            value: arg.expose_value(),
+            sensitive: arg.is_sensitive(),
+            kind: arg.kind,
+        }
+    }
+}
+// ────────────────────────────────────────────────────────────
+/// This enum represents whether a resource is managed or read from a data
+/// source.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    #[default]
+    Managed,
+    Data,
+}
+/// This struct represents a resource, which has one instance per `count`
+/// or `for_each` key.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+pub struct Resource {
+    /// address of the module the resource belongs to, e.g `module.network`.
+    /// It is unset for resources of the root module.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<String>,
+    pub mode: Mode,
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub name: String,
+    /// address of the provider configuration, e.g
+    /// `provider["registry.terraform.io/hashicorp/aws"]`
+    pub provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub each: Option<String>,
+    #[serde(default)]
+    pub instances: Vec<Instance>,
+}
+impl Resource {
+    /// returns address of the resource, e.g `module.network.aws_subnet.this`
+    pub fn address(&self) -> String {
+        let mut res = String::new();
+        if let Some(module) = &self.module {
+            res.push_str(module);
+            res.push('.');
+        }
+        if self.mode == Mode::Data {
+            res.push_str("data.");
+        }
+        res.push_str(self.kind.as_str());
+        res.push('.');
+        res.push_str(self.name.as_str());
+        res
+    }
+    /// returns address of an instance of the resource, e.g
+    /// `aws_subnet.this[0]`
+    pub fn instance_address(&self, instance: &Instance) -> String {
+        match &instance.index_key {
+            Some(key) => format!("{}[{key}]", self.address()),
+            None => self.address(),
+        }
+    }
+}
+/// This enum represents the key of a resource instance.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum IndexKey {
+    /// `count` index
+    Int(i64),
+    /// `for_each` key
+    String(String),
+}
+impl std::fmt::Display for IndexKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IndexKey::Int(index) => write!(f, "{index}"),
+            // NOTE: keys are quoted and escaped the same way as JSON strings
+            IndexKey::String(key) => {
+                write!(f, "{}", serde_json::Value::String(key.clone()))
+            }
+        }
+    }
+}
+/// This struct represents a step of an attribute path, e.g
+/// `{"type": "get_attr", "value": "password"}`.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+pub struct PathStep {
+    /// `get_attr` or `index`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub value: serde_json::Value,
+}
+/// This struct represents an instance of a resource.
+#[derive(PartialEq, Clone, Default, Deserialize, Serialize, Getters)]
+#[getset(get = "pub with_prefix")]
+pub struct Instance {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_key: Option<IndexKey>,
+    #[serde(default)]
+    pub schema_version: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<serde_json::Value>,
+    /// attributes in the legacy flatmap format
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes_flat: Option<BTreeMap<String, String>>,
+    /// paths of attributes that are sensitive
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sensitive_attributes: Vec<Vec<PathStep>>,
+    /// base64-encoded provider data
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private: Option<String>,
+    /// addresses of resources the instance depends on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create_before_destroy: Option<bool>,
+    /// `tainted` if the instance needs to be replaced
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    /// key of a deposed object, which is an instance that was replaced but
+    /// not destroyed yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposed: Option<String>,
+}
+impl Instance {
+    /// returns true if the attribute at the given path is sensitive, e.g
+    /// `["password"]`
+    pub fn is_sensitive(&self, path: &[&str]) -> bool {
+        self.sensitive_attributes.iter().any(|steps| {
+            steps.len() <= path.len()
+                && steps.iter().zip(path).all(|(step, name)| {
+                    step.value.as_str() == Some(name)
+                        || step.value["value"].as_str() == Some(name)
+                })
+        })
+    }
+}
+impl std::fmt::Debug for Instance {
+    /// SECURITY: attributes listed in `sensitive_attributes` are redacted
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let attributes = self.attributes.clone().map(|mut attributes| {
+            for steps in &self.sensitive_attributes {
+                redact(&mut attributes, steps);
+            }
+            attributes
+        });
+        let attributes_flat = self.attributes_flat.as_ref().map(|flat| {
+            let paths: Vec<String> = self
+                .sensitive_attributes
+                .iter()
+                .map(|v| flat_path(v))
+                .collect();
+            flat.iter()
+                .map(|(key, value)| {
+                    let sensitive = paths.iter().any(|path| {
+                        key == path || key.starts_with(&format!("{path}."))
+                    });
+                    match sensitive {
+                        true => (key.as_str(), REDACTED),
+                        false => (key.as_str(), value.as_str()),
+                    }
+                })
+                .collect::<BTreeMap<_, _>>()
+        });
+        f.debug_struct("Instance")
+            .field("index_key", &self.index_key)
+            .field("schema_version", &self.schema_version)
+            .field("attributes", &attributes)
+            .field("attributes_flat", &attributes_flat)
+            .field("sensitive_attributes", &self.sensitive_attributes)
+            .field("private", &self.private)
+            .field("dependencies", &self.dependencies)
+            .field("create_before_destroy", &self.create_before_destroy)
+            .field("status", &self.status)
+            .field("deposed", &self.deposed)
+            .finish()
+    }
+}
+/// value that replaces sensitive attributes in `Debug` output
+const REDACTED: &str = "***";
+/// returns the name or key a path step refers to, e.g `password` or `0`
+fn step_key(step: &PathStep) -> serde_json::Value {
+    match step.value.get("value") {
+        // NOTE: `index` steps hold a typed value, e.g
+        // `{"type": "number", "value": 0}`
+        Some(value) => value.clone(),
+        None => step.value.clone(),
+    }
+}
+/// replaces the value at the given path with `REDACTED`
+fn redact(value: &mut serde_json::Value, steps: &[PathStep]) {
+    let Some((step, rest)) = steps.split_first() else {
+        *value = serde_json::Value::String(REDACTED.to_string());
+        return;
+    };
+    let child = match (value, step_key(step)) {
+        (serde_json::Value::Object(map), serde_json::Value::String(key)) => {
+            map.get_mut(key.as_str())
+        }
+        (serde_json::Value::Array(list), serde_json::Value::Number(index)) => {
+            index
+                .as_u64()
+                .and_then(|index| list.get_mut(usize::try_from(index).ok()?))
+        }
+        _ => None,
+    };
+    if let Some(child) = child {
+        redact(child, rest);
+    }
+}
+/// returns the key of the given path in the legacy flatmap format, e.g
+/// `tags.password`
+fn flat_path(steps: &[PathStep]) -> String {
+    steps
+        .iter()
+        .map(|step| match step_key(step) {
+            serde_json::Value::String(key) => key,
+            key => key.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "state::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "state::tests" --nocapture'
+    use {super::*, serde_json::json};
+    fn fixture() -> serde_json::Value {
+        json!({
+            "version": 4,
+            "terraform_version": "1.5.7",
+            "serial": 12,
+            "lineage": "b6a8c1a2-6c3f-4a4e-8f5e-0b1f7a7e8d11",
+            "outputs": {
+                "endpoint": { "value": "db.example.com", "type": "string" },
+                "password": {
+                    "value": "hunter2",
+                    "type": "string",
+                    "sensitive": true
+                }
+            },
+            "resources": [
+                {
+                    "mode": "data",
+                    "type": "aws_ami",
+                    "name": "ubuntu",
+                    "provider": "provider[\"registry.terraform.io/hashicorp/aws\"]",
+                    "instances": [{ "schema_version": 0, "attributes": {} }]
+                },
+                {
+                    "module": "module.db",
+                    "mode": "managed",
+                    "type": "aws_db_instance",
+                    "name": "this",
+                    "provider": "provider[\"registry.terraform.io/hashicorp/aws\"]",
+                    "instances": [
+                        {
+                            "index_key": 0,
+                            "schema_version": 2,
+                            "attributes": { "password": "hunter2" },
+                            "sensitive_attributes": [
+                                [{ "type": "get_attr", "value": "password" }]
+                            ],
+                            "dependencies": ["data.aws_ami.ubuntu"]
+                        }
+                    ]
+                },
+                {
+                    "mode": "managed",
+                    "type": "aws_instance",
+                    "name": "web",
+                    "provider": "provider[\"registry.terraform.io/hashicorp/aws\"]",
+                    "instances": [
+                        { "index_key": "blue", "schema_version": 1, "attributes": {} },
+                        {
+                            "index_key": "blue",
+                            "deposed": "00000001",
+                            "schema_version": 1,
+                            "attributes": {}
+                        }
+                    ]
+                }
+            ]
+        })
+    }
+    #[test]
+    fn test_addresses() -> Result<(), Error> {
+        let state = State::from_slice(fixture().to_string().as_bytes())?;
+        assert_eq!(
+            state.addresses(),
+            vec![
+                "data.aws_ami.ubuntu",
+                "module.db.aws_db_instance.this[0]",
+                "aws_instance.web[\"blue\"]",
+            ]
+        );
+        let (resource, instance) =
+            state.lookup("module.db.aws_db_instance.this[0]").unwrap();
+        assert_eq!(resource.kind, "aws_db_instance");
+        assert!(instance.is_sensitive(&["password"]));
+        assert!(!instance.is_sensitive(&["username"]));
+        assert_eq!(instance.dependencies, vec!["data.aws_ami.ubuntu"]);
+        assert!(state.lookup("aws_instance.web").is_none());
+        Ok(())
+    }
+    #[test]
+    fn test_sensitive_attributes_debug() -> Result<(), serde_json::Error> {
+        let mut input = fixture();
+        input["resources"][1]["instances"][0]["attributes"] = json!({
+            "username": "admin",
+            "password": "hunter2",
+            "tags": { "Name": "db", "Token": "s3cr3t" },
+            "keys": ["public", "pr1vate"]
+        });
+        input["resources"][1]["instances"][0]["attributes_flat"] = json!({
+            "username": "admin",
+            "password": "hunter2",
+            "tags.Token": "s3cr3t"
+        });
+        input["resources"][1]["instances"][0]["sensitive_attributes"] = json!([
+            [{ "type": "get_attr", "value": "password" }],
+            [
+                { "type": "get_attr", "value": "tags" },
+                { "type": "index", "value": { "type": "string", "value": "Token" } }
+            ],
+            [
+                { "type": "get_attr", "value": "keys" },
+                { "type": "index", "value": { "type": "number", "value": 1 } }
+            ]
+        ]);
+        let state: State = serde_json::from_value(input.clone())?;
+        // NOTE: the instance is printed as part of the state too
+        let actual = format!("{state:?}");
+        for secret in ["hunter2", "s3cr3t", "pr1vate"] {
+            assert!(!actual.contains(secret), "`{secret}` was printed");
+        }
+        for value in ["admin", "public", "Name"] {
+            assert!(actual.contains(value), "`{value}` was not printed");
+        }
+        // NOTE: sensitive attributes are serialized unchanged
+        assert_eq!(serde_json::to_value(&state)?, input);
+        Ok(())
+    }
+    #[test]
+    fn test_outputs() -> Result<(), serde_json::Error> {
+        let state: State = serde_json::from_value(fixture())?;
+        let endpoint = state.output("endpoint").unwrap();
+        assert!(!endpoint.is_sensitive());
+        assert_eq!(endpoint.get_value(), Some(&json!("db.example.com")));
+        let password = state.output("password").unwrap();
+        assert!(password.is_sensitive());
+        assert_eq!(password.get_value(), None);
+        assert!(!format!("{password:?}").contains("hunter2"));
+        assert_eq!(password.expose_value(), json!("hunter2"));
+        // NOTE: sensitive outputs are serialized unchanged
+        assert_eq!(
+            serde_json::to_value(&state)?["outputs"]["password"],
+            fixture()["outputs"]["password"]
+        );
+        Ok(())
+    }
+    #[test]
+    fn test_unsupported_version() {
+        let mut input = fixture();
+        input["version"] = json!(3);
+        assert!(State::from_slice(input.to_string().as_bytes()).is_err());
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
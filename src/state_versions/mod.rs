@@ -0,0 +1,484 @@
+//! This module implements the state versions API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/state-versions).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let state_versions = client.state_versions();
+//!     let current = state_versions
+//!         .read_current("ws-4j8p6jX1w33MiDC7")
+//!         .await
+//!         .unwrap()
+//!         .into_data();
+//!     let state = state_versions
+//!         .download(current.id.as_deref().unwrap())
+//!         .await
+//!         .unwrap();
+//!     println!("{}", String::from_utf8_lossy(&state));
+//! }
+//! ```
+/// This module implements state version outputs.
+pub mod outputs;
+// ────────────────────────────────────────────────────────────
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient, Secret},
+        jsonapi::{self, Many, One, Relationship},
+        pagination::PaginatorOptions,
+    },
+    bytes::Bytes,
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a state version resource.
+pub type StateVersion = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single state version.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents possible states of a state version.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// the state version was created, but its state was not uploaded yet
+    #[default]
+    Pending,
+    Finalized,
+    Discarded,
+    BackingDataSoftDeleted,
+    BackingDataPermanentlyDeleted,
+    /// a status that is not known to this library
+    #[serde(other)]
+    Unknown,
+}
+/// This struct represents attributes of a state version.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    #[serde(default)]
+    pub status: Status,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    /// URL the raw state is downloaded from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hosted_state_download_url: Option<String>,
+    /// URL the JSON state, i.e `terraform show -json` output, is downloaded
+    /// from
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hosted_json_state_download_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub intermediate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modules: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub providers: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources: Option<serde_json::Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resources_processed: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub serial: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_version: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_commit_sha: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_commit_url: Option<String>,
+}
+/// This struct represents relationships of a state version.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_by: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outputs: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing state versions.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of state versions per page
+    pub page_size: Option<u32>,
+    /// related resources to include, e.g `outputs`
+    pub include: Vec<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut res = crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+        ]);
+        if !self.include.is_empty() {
+            res.push(("include".to_string(), self.include.join(",")));
+        }
+        res
+    }
+}
+/// This struct contains options for creating a state version.
+///
+/// MD5 checksum and base64 encoding of the state are computed when the
+/// request is sent.
+#[derive(PartialEq, Eq, Debug, Clone, Default)]
+pub struct CreateOptions {
+    /// raw state file, i.e `terraform.tfstate`
+    pub state: Vec<u8>,
+    /// serial of the state. It is read from the state when unset, and must
+    /// match the state otherwise.
+    pub serial: Option<u64>,
+    /// lineage of the state. It is read from the state when unset, and must
+    /// match the state otherwise.
+    pub lineage: Option<String>,
+    /// JSON state, i.e `terraform show -json` output, which the server uses
+    /// to extract resources and outputs
+    pub json_state: Option<Vec<u8>>,
+    /// outputs section of the JSON state
+    pub json_state_outputs: Option<Vec<u8>>,
+    /// creates the state version even if its lineage or serial conflict
+    /// with the current state version
+    pub force: Option<bool>,
+    /// run the state version is associated with
+    pub run_id: Option<String>,
+}
+/// This struct represents attributes of a state version creation request.
+///
+/// SECURITY: the state may contain secrets, so its encoded forms are held in
+/// `Secret`, which keeps them out of `Debug` output, e.g request traces.
+#[derive(PartialEq, Eq, Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case")]
+struct CreateAttributes {
+    serial: u64,
+    md5: String,
+    lineage: String,
+    state: Secret,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_state: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    json_state_outputs: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    force: Option<bool>,
+}
+/// returns request document for creating a state version after checking
+/// that serial and lineage of the options match the state.
+fn create_request(
+    options: &CreateOptions,
+) -> Result<jsonapi::Request<CreateAttributes>, Error> {
+    let invalid = |reason: String| Error::InvalidInput { reason };
+    let state: serde_json::Value =
+        serde_json::from_slice(options.state.as_slice())
+            .map_err(|e| invalid(format!("state is not valid JSON: {e}")))?;
+    let serial = match (state["serial"].as_u64(), options.serial) {
+        (Some(actual), Some(expected)) if actual != expected => {
+            return Err(invalid(format!(
+                "serial {expected} does not match serial {actual} of the state"
+            )));
+        }
+        (Some(serial), _) | (None, Some(serial)) => serial,
+        (None, None) => {
+            return Err(invalid("state does not have a serial".to_string()));
+        }
+    };
+    let lineage = match (state["lineage"].as_str(), &options.lineage) {
+        (Some(actual), Some(expected)) if actual != expected => {
+            return Err(invalid(format!(
+                "lineage `{expected}` does not match lineage `{actual}` of the state"
+            )));
+        }
+        (Some(lineage), _) => lineage.to_string(),
+        (None, Some(lineage)) => lineage.clone(),
+        (None, None) => {
+            return Err(invalid("state does not have a lineage".to_string()));
+        }
+    };
+    let encode = |v: &Vec<u8>| Secret::new(base64::encode(v));
+    let attributes = CreateAttributes {
+        serial,
+        md5: format!("{:x}", md5::compute(options.state.as_slice())),
+        lineage,
+        state: encode(&options.state),
+        json_state: options.json_state.as_ref().map(encode),
+        json_state_outputs: options.json_state_outputs.as_ref().map(encode),
+        force: options.force,
+    };
+    let mut request =
+        jsonapi::Request::builder("state-versions").attributes(attributes);
+    if let Some(run_id) = &options.run_id {
+        request = request.to_one("run", "runs", Some(run_id));
+    }
+    Ok(request.build())
+}
+// ────────────────────────────────────────────────────────────
+/// StateVersions implements the state versions API.
+#[derive(Debug, Clone)]
+pub struct StateVersions<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns state versions API
+    pub fn state_versions(&self) -> StateVersions<'_> {
+        StateVersions { client: self }
+    }
+}
+impl<'a> StateVersions<'a> {
+    /// lists state versions of a workspace, newest first
+    pub async fn list(
+        &self,
+        organization: &str,
+        workspace_name: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<StateVersion>, Error> {
+        validate_id("organization", organization)?;
+        validate_id("workspace name", workspace_name)?;
+        let query = list_query(organization, workspace_name, options);
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get("state-versions", None, Some(&crate::core::query(&query)))
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns state versions of a workspace, newest first, across all pages.
+    /// The stream ends after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        organization: &str,
+        workspace_name: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<StateVersion, Error>>, Error> {
+        validate_id("organization", organization)?;
+        validate_id("workspace name", workspace_name)?;
+        Ok(self.client.paginate(
+            "state-versions",
+            &list_query(organization, workspace_name, options),
+            paginator,
+        ))
+    }
+    /// creates a state version in a workspace, which becomes its current
+    /// state. The workspace needs to be locked by the caller.
+    pub async fn create(
+        &self,
+        workspace_id: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        if let Some(run_id) = &options.run_id {
+            validate_id("run ID", run_id)?;
+        }
+        let payload = create_request(options)?;
+        self.client
+            .post(
+                format!("workspaces/{workspace_id}/state-versions"),
+                None,
+                payload,
+            )
+            .await
+    }
+    /// reads a state version
+    pub async fn read(
+        &self,
+        state_version_id: &str,
+    ) -> Result<Response, Error> {
+        validate_id("state version ID", state_version_id)?;
+        self.client
+            .get(format!("state-versions/{state_version_id}"), None, None)
+            .await
+    }
+    /// reads the current state version of a workspace
+    pub async fn read_current(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .get(
+                format!("workspaces/{workspace_id}/current-state-version"),
+                None,
+                None,
+            )
+            .await
+    }
+    /// downloads the raw state of a state version
+    pub async fn download(
+        &self,
+        state_version_id: &str,
+    ) -> Result<Bytes, Error> {
+        let state_version = self.read(state_version_id).await?.into_data();
+        let url = state_version
+            .attributes
+            .and_then(|attributes| attributes.hosted_state_download_url)
+            .filter(|url| !url.is_empty())
+            .ok_or_else(|| Error::Deserialize {
+                path: "data.attributes.hosted-state-download-url".to_string(),
+                reason: format!(
+                    "state version `{state_version_id}` does not have a download URL"
+                ),
+            })?;
+        self.client.download(url.as_str(), None).await
+    }
+    /// lists outputs of a state version. Values of sensitive outputs are
+    /// not returned.
+    pub async fn list_outputs(
+        &self,
+        state_version_id: &str,
+    ) -> Result<Vec<outputs::Output>, Error> {
+        validate_id("state version ID", state_version_id)?;
+        let response: jsonapi::SuccessList<
+            outputs::Attributes,
+            outputs::Relationships,
+        > = self
+            .client
+            .get(
+                format!("state-versions/{state_version_id}/outputs"),
+                None,
+                None,
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// lists outputs of the current state version of a workspace. Values of
+    /// sensitive outputs are not returned.
+    pub async fn list_current_outputs(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<outputs::Output>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        let response: jsonapi::SuccessList<
+            outputs::Attributes,
+            outputs::Relationships,
+        > = self
+            .client
+            .get(
+                format!(
+                    "workspaces/{workspace_id}/current-state-version-outputs"
+                ),
+                None,
+                None,
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// reads a state version output, including its value if it is sensitive
+    pub async fn read_output(
+        &self,
+        output_id: &str,
+    ) -> Result<outputs::Response, Error> {
+        validate_id("state version output ID", output_id)?;
+        self.client
+            .get(format!("state-version-outputs/{output_id}"), None, None)
+            .await
+    }
+}
+/// returns query parameters for listing state versions of a workspace
+fn list_query(
+    organization: &str,
+    workspace_name: &str,
+    options: &ListOptions,
+) -> Vec<(String, String)> {
+    let mut res = options.to_query();
+    res.push((
+        "filter[organization][name]".to_string(),
+        organization.to_string(),
+    ));
+    res.push((
+        "filter[workspace][name]".to_string(),
+        workspace_name.to_string(),
+    ));
+    res
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "state_versions::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "state_versions::tests" --nocapture'
+    use {super::*, serde_json::json};
+    const STATE: &str = r#"{
+        "version": 4,
+        "terraform_version": "1.5.0",
+        "serial": 3,
+        "lineage": "8d6c4d3e-3e1b-4d2c-9c7e-2f1f4d9b2a11",
+        "outputs": {},
+        "resources": []
+    }"#;
+    #[test]
+    fn test_create_request() -> Result<(), Error> {
+        let options = CreateOptions {
+            state: STATE.as_bytes().to_vec(),
+            run_id: Some("run-123".to_string()),
+            ..Default::default()
+        };
+        let actual = serde_json::to_value(create_request(&options)?).unwrap();
+        let attributes = &actual["data"]["attributes"];
+        assert_eq!(attributes["serial"], json!(3));
+        assert_eq!(
+            attributes["lineage"],
+            json!("8d6c4d3e-3e1b-4d2c-9c7e-2f1f4d9b2a11")
+        );
+        assert_eq!(
+            attributes["md5"],
+            json!(format!("{:x}", md5::compute(STATE)))
+        );
+        assert_eq!(
+            base64::decode(attributes["state"].as_str().unwrap()).unwrap(),
+            STATE.as_bytes()
+        );
+        assert_eq!(
+            actual["data"]["relationships"]["run"]["data"]["id"],
+            json!("run-123")
+        );
+        // SECURITY: traces of the request do not print the state
+        let actual = format!("{:#?}", create_request(&options)?);
+        assert!(!actual.contains(&base64::encode(STATE)));
+        assert!(actual.contains("Secret(***)"));
+        Ok(())
+    }
+    #[test]
+    fn test_create_request_mismatch() {
+        let options = CreateOptions {
+            state: STATE.as_bytes().to_vec(),
+            serial: Some(4),
+            ..Default::default()
+        };
+        assert!(create_request(&options).is_err());
+        let options = CreateOptions {
+            state: STATE.as_bytes().to_vec(),
+            lineage: Some("other".to_string()),
+            ..Default::default()
+        };
+        assert!(create_request(&options).is_err());
+        let options = CreateOptions {
+            state: b"not json".to_vec(),
+            ..Default::default()
+        };
+        assert!(create_request(&options).is_err());
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
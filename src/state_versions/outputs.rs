@@ -0,0 +1,80 @@
+//! This module implements state version outputs.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/state-version-outputs).
+use {
+    crate::jsonapi,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a state version output resource.
+pub type Output = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single state version
+/// output.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This struct represents attributes of a state version output.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub name: String,
+    #[serde(default)]
+    pub sensitive: bool,
+    /// type of the value, e.g `string` or `array`
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+    /// value of the output. Values of sensitive outputs are only returned by
+    /// the endpoint that reads a single output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<serde_json::Value>,
+    /// type of the value in Terraform's JSON type notation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detailed_type: Option<serde_json::Value>,
+}
+/// This struct represents relationships of a state version output.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "state_versions::outputs::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "state_versions::outputs::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/state-version-outputs
+        let input = json!({
+            "data": {
+                "id": "wsout-J2zM24JPFbfc7bE5",
+                "type": "state-version-outputs",
+                "attributes": {
+                    "name": "flavor",
+                    "sensitive": false,
+                    "type": "string",
+                    "value": "Peanut Butter",
+                    "detailed-type": "string"
+                },
+                "links": {
+                    "self": "/api/v2/state-version-outputs/wsout-J2zM24JPFbfc7bE5"
+                }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.name, "flavor");
+        assert_eq!(attributes.kind.as_deref(), Some("string"));
+        assert_eq!(attributes.value, Some(json!("Peanut Butter")));
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,392 @@
+//! This module implements the team access API, which grants teams access
+//! to workspaces.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/team-access).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! use tfe_oxide::team_access::{Access, AddOptions, RunsPermission};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = AddOptions {
+//!         access: Access::Custom,
+//!         runs: Some(RunsPermission::Plan),
+//!         ..Default::default()
+//!     };
+//!     let access = client
+//!         .team_access()
+//!         .add("team-6p5jTwJQXwqZBncC", "ws-4j8p6jX1w33MiDC7", &options)
+//!         .await
+//!         .unwrap();
+//!     println!("{:?}", access.get_data().get_id());
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, One, Relationship},
+        pagination::PaginatorOptions,
+    },
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a team access resource.
+pub type TeamAccess = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single team access.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents the access level of a team on a workspace.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    /// read runs, variables and state versions
+    #[default]
+    Read,
+    /// read access and queuing plans
+    Plan,
+    /// plan access and approving runs, editing variables and locking
+    Write,
+    /// write access and managing settings and team access
+    Admin,
+    /// permissions are set one by one with the custom permission flags
+    Custom,
+    #[serde(other)]
+    Unknown,
+}
+/// This enum represents custom permission on runs.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RunsPermission {
+    Read,
+    Plan,
+    Apply,
+    #[serde(other)]
+    Unknown,
+}
+/// This enum represents custom permission on variables.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariablesPermission {
+    None,
+    Read,
+    Write,
+    #[serde(other)]
+    Unknown,
+}
+/// This enum represents custom permission on state versions.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum StateVersionsPermission {
+    None,
+    /// read outputs of the current state version only
+    ReadOutputs,
+    Read,
+    Write,
+    #[serde(other)]
+    Unknown,
+}
+/// This enum represents custom permission on Sentinel mocks.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SentinelMocksPermission {
+    None,
+    Read,
+    #[serde(other)]
+    Unknown,
+}
+/// This struct represents attributes of a team access.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub access: Access,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<RunsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<VariablesPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_versions: Option<StateVersionsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sentinel_mocks: Option<SentinelMocksPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_locking: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_tasks: Option<bool>,
+}
+/// This struct represents relationships of a team access.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing team accesses of a workspace.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of team accesses per page
+    pub page_size: Option<u32>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+        ])
+    }
+}
+/// This struct contains options for granting a team access to a workspace.
+///
+/// NOTE: custom permission flags are only used with `Access::Custom`.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AddOptions {
+    pub access: Access,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<RunsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<VariablesPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_versions: Option<StateVersionsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sentinel_mocks: Option<SentinelMocksPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_locking: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_tasks: Option<bool>,
+}
+/// This struct contains options for updating a team access. Unset fields
+/// are left unchanged.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access: Option<Access>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<RunsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<VariablesPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_versions: Option<StateVersionsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sentinel_mocks: Option<SentinelMocksPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_locking: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_tasks: Option<bool>,
+}
+// ────────────────────────────────────────────────────────────
+/// returns request document for granting a team access to a workspace
+fn add_request<'a>(
+    team_id: &str,
+    workspace_id: &str,
+    options: &'a AddOptions,
+) -> jsonapi::Request<&'a AddOptions> {
+    jsonapi::Request::builder("team-workspaces")
+        .attributes(options)
+        .to_one("team", "teams", Some(team_id))
+        .to_one("workspace", "workspaces", Some(workspace_id))
+        .build()
+}
+// ────────────────────────────────────────────────────────────
+/// TeamAccesses implements the team access API.
+#[derive(Debug, Clone)]
+pub struct TeamAccesses<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns team access API
+    pub fn team_access(&self) -> TeamAccesses<'_> {
+        TeamAccesses { client: self }
+    }
+}
+impl<'a> TeamAccesses<'a> {
+    /// lists team accesses of a workspace
+    pub async fn list(
+        &self,
+        workspace_id: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<TeamAccess>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        let query = list_query(workspace_id, options);
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                "team-workspaces".to_string(),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns team accesses of a workspace across all pages. The stream ends
+    /// after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        workspace_id: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<TeamAccess, Error>>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        Ok(self.client.paginate(
+            "team-workspaces",
+            &list_query(workspace_id, options),
+            paginator,
+        ))
+    }
+    /// grants a team access to a workspace
+    pub async fn add(
+        &self,
+        team_id: &str,
+        workspace_id: &str,
+        options: &AddOptions,
+    ) -> Result<Response, Error> {
+        validate_id("team ID", team_id)?;
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .post(
+                "team-workspaces".to_string(),
+                None,
+                add_request(team_id, workspace_id, options),
+            )
+            .await
+    }
+    /// reads a team access
+    pub async fn read(&self, access_id: &str) -> Result<Response, Error> {
+        validate_id("team access ID", access_id)?;
+        self.client
+            .get(format!("team-workspaces/{access_id}"), None, None)
+            .await
+    }
+    /// updates a team access
+    pub async fn update(
+        &self,
+        access_id: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("team access ID", access_id)?;
+        let payload = jsonapi::Request::builder("team-workspaces")
+            .attributes(options)
+            .build();
+        self.client
+            .patch(format!("team-workspaces/{access_id}"), None, payload)
+            .await
+    }
+    /// revokes a team access
+    pub async fn remove(&self, access_id: &str) -> Result<(), Error> {
+        validate_id("team access ID", access_id)?;
+        self.client
+            .delete(format!("team-workspaces/{access_id}"), None, ())
+            .await
+    }
+}
+/// returns query parameters for listing team accesses of a workspace
+fn list_query(
+    workspace_id: &str,
+    options: &ListOptions,
+) -> Vec<(String, String)> {
+    let mut res = options.to_query();
+    res.push((
+        "filter[workspace][id]".to_string(),
+        workspace_id.to_string(),
+    ));
+    res
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "team_access::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "team_access::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/team-access
+        let input = json!({
+            "data": {
+                "id": "tws-s68jV4FWCDwWvQq8",
+                "type": "team-workspaces",
+                "attributes": {
+                    "access": "custom",
+                    "runs": "read",
+                    "variables": "none",
+                    "state-versions": "read-outputs",
+                    "sentinel-mocks": "none",
+                    "workspace-locking": false,
+                    "run-tasks": false
+                },
+                "relationships": {
+                    "team": {
+                        "data": { "id": "team-DBycxkdQrGFf5zEM", "type": "teams" },
+                        "links": { "related": "/api/v2/teams/team-DBycxkdQrGFf5zEM" }
+                    },
+                    "workspace": {
+                        "data": { "id": "ws-XGA52YVykdTgryTN", "type": "workspaces" },
+                        "links": { "related": "/api/v2/organizations/my-organization/workspaces/my-workspace" }
+                    }
+                },
+                "links": { "self": "/api/v2/team-workspaces/tws-s68jV4FWCDwWvQq8" }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.access, Access::Custom);
+        assert_eq!(
+            attributes.state_versions,
+            Some(StateVersionsPermission::ReadOutputs)
+        );
+        Ok(())
+    }
+    #[test]
+    fn test_add_request() {
+        let options = AddOptions {
+            access: Access::Custom,
+            runs: Some(RunsPermission::Apply),
+            variables: Some(VariablesPermission::None),
+            ..Default::default()
+        };
+        let expected = json!({
+            "data": {
+                "type": "team-workspaces",
+                "attributes": {
+                    "access": "custom",
+                    "runs": "apply",
+                    "variables": "none"
+                },
+                "relationships": {
+                    "team": {
+                        "data": { "type": "teams", "id": "team-123" }
+                    },
+                    "workspace": {
+                        "data": { "type": "workspaces", "id": "ws-123" }
+                    }
+                }
+            }
+        });
+        let actual = add_request("team-123", "ws-123", &options);
+        assert_eq!(serde_json::to_value(actual).unwrap(), expected);
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,174 @@
+//! This module implements the team membership API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/team-members).
+//!
+//! Members are added or removed either by username, or by ID of their
+//! organization membership. The latter also works for users who have been
+//! invited to the organization but have not accepted the invitation yet.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     client
+//!         .team_members()
+//!         .add_by_usernames("team-6p5jTwJQXwqZBncC", &["octocat"])
+//!         .await
+//!         .unwrap();
+//! }
+//! ```
+use crate::{
+    client::{validate_id, Client},
+    core::{Error, HttpClient},
+    jsonapi::Relationship,
+};
+// ────────────────────────────────────────────────────────────
+/// TeamMembers implements the team membership API.
+#[derive(Debug, Clone)]
+pub struct TeamMembers<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns team membership API
+    pub fn team_members(&self) -> TeamMembers<'_> {
+        TeamMembers { client: self }
+    }
+}
+impl<'a> TeamMembers<'a> {
+    /// adds users to a team by their usernames
+    pub async fn add_by_usernames(
+        &self,
+        team_id: &str,
+        usernames: &[&str],
+    ) -> Result<(), Error> {
+        self.send(team_id, "users", usernames, true).await
+    }
+    /// removes users from a team by their usernames
+    pub async fn remove_by_usernames(
+        &self,
+        team_id: &str,
+        usernames: &[&str],
+    ) -> Result<(), Error> {
+        self.send(team_id, "users", usernames, false).await
+    }
+    /// adds users to a team by IDs of their organization memberships
+    pub async fn add_by_membership_ids(
+        &self,
+        team_id: &str,
+        membership_ids: &[&str],
+    ) -> Result<(), Error> {
+        self.send(team_id, "organization-memberships", membership_ids, true)
+            .await
+    }
+    /// removes users from a team by IDs of their organization memberships
+    pub async fn remove_by_membership_ids(
+        &self,
+        team_id: &str,
+        membership_ids: &[&str],
+    ) -> Result<(), Error> {
+        self.send(team_id, "organization-memberships", membership_ids, false)
+            .await
+    }
+    /// adds resources to, or removes them from, a to-many relationship of a
+    /// team
+    async fn send(
+        &self,
+        team_id: &str,
+        relationship: &str,
+        ids: &[&str],
+        add: bool,
+    ) -> Result<(), Error> {
+        validate_id("team ID", team_id)?;
+        if ids.is_empty() {
+            return Err(Error::InvalidInput {
+                reason: "at least one team member is required".to_string(),
+            });
+        }
+        for id in ids {
+            validate_id("team member", id)?;
+        }
+        let path = format!("teams/{team_id}/relationships/{relationship}");
+        let payload = Relationship::many(relationship, ids);
+        match add {
+            true => self.client.post(path, None, payload).await,
+            false => self.client.delete(path, None, payload).await,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "team_members::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "team_members::tests" --nocapture'
+    use {
+        super::*,
+        crate::testing::{Reply, Server},
+        serde_json::json,
+    };
+    #[tokio::test]
+    async fn test_add_and_remove() {
+        let server = Server::start(vec![
+            Reply::status(204, ""),
+            Reply::status(204, ""),
+        ])
+        .await;
+        let client = server.client();
+        client
+            .team_members()
+            .add_by_usernames("team-123", &["octocat", "hubot"])
+            .await
+            .unwrap();
+        client
+            .team_members()
+            .remove_by_membership_ids("team-123", &["ou-123"])
+            .await
+            .unwrap();
+        let received = server.received();
+        assert_eq!(received[0].method, "POST");
+        assert_eq!(
+            received[0].target,
+            "/api/v2/teams/team-123/relationships/users"
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&received[0].body)
+                .unwrap(),
+            json!({
+                "data": [
+                    { "type": "users", "id": "octocat" },
+                    { "type": "users", "id": "hubot" }
+                ]
+            })
+        );
+        assert_eq!(received[1].method, "DELETE");
+        assert_eq!(
+            received[1].target,
+            "/api/v2/teams/team-123/relationships/organization-memberships"
+        );
+        assert_eq!(
+            serde_json::from_slice::<serde_json::Value>(&received[1].body)
+                .unwrap(),
+            json!({
+                "data": [{ "type": "organization-memberships", "id": "ou-123" }]
+            })
+        );
+    }
+    #[tokio::test]
+    async fn test_add_without_members() {
+        let server = Server::start(vec![]).await;
+        let actual = server
+            .client()
+            .team_members()
+            .add_by_usernames("team-123", &[])
+            .await
+            .unwrap_err();
+        assert!(matches!(actual, Error::InvalidInput { .. }));
+        assert!(server.received().is_empty());
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,431 @@
+//! This module implements the team project access API, which grants teams
+//! access to projects and their workspaces.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/project-team-access).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! use tfe_oxide::team_project_access::{Access, AddOptions};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = AddOptions {
+//!         access: Access::Maintain,
+//!         ..Default::default()
+//!     };
+//!     let access = client
+//!         .team_project_access()
+//!         .add("team-6p5jTwJQXwqZBncC", "prj-WsVcWRr7SSxRqvGA", &options)
+//!         .await
+//!         .unwrap();
+//!     println!("{:?}", access.get_data().get_id());
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, One, Relationship},
+        pagination::PaginatorOptions,
+        team_access::{
+            RunsPermission, SentinelMocksPermission, StateVersionsPermission,
+            VariablesPermission,
+        },
+    },
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a team project access resource.
+pub type TeamProjectAccess = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single team project
+/// access.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents the access level of a team on a project.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Access {
+    /// read the project and its workspaces
+    #[default]
+    Read,
+    /// write access on workspaces of the project
+    Write,
+    /// write access and managing and deleting workspaces
+    Maintain,
+    /// maintain access and managing the project and its team access
+    Admin,
+    /// permissions are set one by one with the custom permission flags
+    Custom,
+    #[serde(other)]
+    Unknown,
+}
+/// This enum represents custom permission on settings of a project.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SettingsPermission {
+    Read,
+    Update,
+    Delete,
+    #[serde(other)]
+    Unknown,
+}
+/// This enum represents custom permission on team access of a project.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TeamsPermission {
+    None,
+    Read,
+    Manage,
+    #[serde(other)]
+    Unknown,
+}
+/// This enum represents custom permission on variable sets of a project.
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VariableSetsPermission {
+    None,
+    Read,
+    Write,
+    #[serde(other)]
+    Unknown,
+}
+/// This struct represents custom permissions on a project itself.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct ProjectAccess {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub settings: Option<SettingsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub teams: Option<TeamsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variable_sets: Option<VariableSetsPermission>,
+}
+/// This struct represents custom permissions on all workspaces of a
+/// project.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct WorkspaceAccess {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delete: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locking: Option<bool>,
+    #[serde(rename = "move", skip_serializing_if = "Option::is_none")]
+    pub move_workspaces: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_tasks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<RunsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sentinel_mocks: Option<SentinelMocksPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_versions: Option<StateVersionsPermission>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<VariablesPermission>,
+}
+/// This struct represents attributes of a team project access.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub access: Access,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_access: Option<ProjectAccess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_access: Option<WorkspaceAccess>,
+}
+/// This struct represents relationships of a team project access.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing team accesses of a project.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of team accesses per page
+    pub page_size: Option<u32>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+        ])
+    }
+}
+/// This struct contains options for granting a team access to a project.
+///
+/// NOTE: custom permissions are only used with `Access::Custom`.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct AddOptions {
+    pub access: Access,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_access: Option<ProjectAccess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_access: Option<WorkspaceAccess>,
+}
+/// This struct contains options for updating a team project access. Unset
+/// fields are left unchanged.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access: Option<Access>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_access: Option<ProjectAccess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_access: Option<WorkspaceAccess>,
+}
+// ────────────────────────────────────────────────────────────
+/// returns request document for granting a team access to a project
+fn add_request<'a>(
+    team_id: &str,
+    project_id: &str,
+    options: &'a AddOptions,
+) -> jsonapi::Request<&'a AddOptions> {
+    jsonapi::Request::builder("team-projects")
+        .attributes(options)
+        .to_one("project", "projects", Some(project_id))
+        .to_one("team", "teams", Some(team_id))
+        .build()
+}
+// ────────────────────────────────────────────────────────────
+/// TeamProjectAccesses implements the team project access API.
+#[derive(Debug, Clone)]
+pub struct TeamProjectAccesses<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns team project access API
+    pub fn team_project_access(&self) -> TeamProjectAccesses<'_> {
+        TeamProjectAccesses { client: self }
+    }
+}
+impl<'a> TeamProjectAccesses<'a> {
+    /// lists team accesses of a project
+    pub async fn list(
+        &self,
+        project_id: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<TeamProjectAccess>, Error> {
+        validate_id("project ID", project_id)?;
+        let query = list_query(project_id, options);
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                "team-projects".to_string(),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns team accesses of a project across all pages. The stream ends
+    /// after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        project_id: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<TeamProjectAccess, Error>>, Error>
+    {
+        validate_id("project ID", project_id)?;
+        Ok(self.client.paginate(
+            "team-projects",
+            &list_query(project_id, options),
+            paginator,
+        ))
+    }
+    /// grants a team access to a project
+    pub async fn add(
+        &self,
+        team_id: &str,
+        project_id: &str,
+        options: &AddOptions,
+    ) -> Result<Response, Error> {
+        validate_id("team ID", team_id)?;
+        validate_id("project ID", project_id)?;
+        self.client
+            .post(
+                "team-projects".to_string(),
+                None,
+                add_request(team_id, project_id, options),
+            )
+            .await
+    }
+    /// reads a team project access
+    pub async fn read(&self, access_id: &str) -> Result<Response, Error> {
+        validate_id("team project access ID", access_id)?;
+        self.client
+            .get(format!("team-projects/{access_id}"), None, None)
+            .await
+    }
+    /// updates a team project access
+    pub async fn update(
+        &self,
+        access_id: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("team project access ID", access_id)?;
+        let payload = jsonapi::Request::builder("team-projects")
+            .attributes(options)
+            .build();
+        self.client
+            .patch(format!("team-projects/{access_id}"), None, payload)
+            .await
+    }
+    /// revokes a team project access
+    pub async fn remove(&self, access_id: &str) -> Result<(), Error> {
+        validate_id("team project access ID", access_id)?;
+        self.client
+            .delete(format!("team-projects/{access_id}"), None, ())
+            .await
+    }
+}
+/// returns query parameters for listing team accesses of a project
+fn list_query(
+    project_id: &str,
+    options: &ListOptions,
+) -> Vec<(String, String)> {
+    let mut res = options.to_query();
+    res.push(("filter[project][id]".to_string(), project_id.to_string()));
+    res
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "team_project_access::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "team_project_access::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/project-team-access
+        let input = json!({
+            "data": {
+                "id": "tprj-WbG7p5KnT7S7HZqw",
+                "type": "team-projects",
+                "attributes": {
+                    "access": "custom",
+                    "project-access": {
+                        "settings": "read",
+                        "teams": "none",
+                        "variable-sets": "write"
+                    },
+                    "workspace-access": {
+                        "create": true,
+                        "move": false,
+                        "locking": false,
+                        "delete": false,
+                        "runs": "read",
+                        "variables": "read",
+                        "state-versions": "read-outputs",
+                        "sentinel-mocks": "none",
+                        "run-tasks": false
+                    }
+                },
+                "relationships": {
+                    "team": {
+                        "data": { "id": "team-DBycxkdQrGFf5zEM", "type": "teams" }
+                    },
+                    "project": {
+                        "data": { "id": "prj-ckZoJwdERaWcFHwi", "type": "projects" }
+                    }
+                },
+                "links": { "self": "/api/v2/team-projects/tprj-WbG7p5KnT7S7HZqw" }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.access, Access::Custom);
+        let project = attributes.project_access.as_ref().unwrap();
+        assert_eq!(project.variable_sets, Some(VariableSetsPermission::Write));
+        let workspace = attributes.workspace_access.as_ref().unwrap();
+        assert_eq!(workspace.move_workspaces, Some(false));
+        assert_eq!(
+            workspace.state_versions,
+            Some(StateVersionsPermission::ReadOutputs)
+        );
+        Ok(())
+    }
+    #[test]
+    fn test_add_request() {
+        let options = AddOptions {
+            access: Access::Custom,
+            project_access: Some(ProjectAccess {
+                settings: Some(SettingsPermission::Update),
+                teams: Some(TeamsPermission::Manage),
+                variable_sets: Some(VariableSetsPermission::Read),
+            }),
+            workspace_access: Some(WorkspaceAccess {
+                create: Some(true),
+                move_workspaces: Some(true),
+                runs: Some(RunsPermission::Apply),
+                state_versions: Some(StateVersionsPermission::Write),
+                ..Default::default()
+            }),
+        };
+        let expected = json!({
+            "data": {
+                "type": "team-projects",
+                "attributes": {
+                    "access": "custom",
+                    "project-access": {
+                        "settings": "update",
+                        "teams": "manage",
+                        "variable-sets": "read"
+                    },
+                    "workspace-access": {
+                        "create": true,
+                        "move": true,
+                        "runs": "apply",
+                        "state-versions": "write"
+                    }
+                },
+                "relationships": {
+                    "project": {
+                        "data": { "type": "projects", "id": "prj-123" }
+                    },
+                    "team": { "data": { "type": "teams", "id": "team-123" } }
+                }
+            }
+        });
+        assert_eq!(
+            serde_json::to_value(add_request("team-123", "prj-123", &options))
+                .unwrap(),
+            expected
+        );
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,380 @@
+//! This module implements the teams API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/teams).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = tfe_oxide::teams::CreateOptions {
+//!         name: "platform".to_string(),
+//!         ..Default::default()
+//!     };
+//!     let team = client
+//!         .teams()
+//!         .create("my-organization", &options)
+//!         .await
+//!         .unwrap();
+//!     println!("{:?}", team.get_data().get_id());
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, One, Relationship},
+        pagination::PaginatorOptions,
+    },
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a team resource.
+pub type Team = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single team.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents who can see a team.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    /// only members of the team and organization owners can see the team
+    #[default]
+    Secret,
+    /// all members of the organization can see the team
+    Organization,
+    #[serde(other)]
+    Unknown,
+}
+/// This struct represents organization-wide permissions of a team.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct OrganizationAccess {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_secret_teams: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_agent_pools: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_membership: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_modules: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_organization_access: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_policies: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_policy_overrides: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_projects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_providers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_run_tasks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_teams: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_vcs_settings: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub manage_workspaces: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_projects: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub read_workspaces: Option<bool>,
+}
+/// This struct represents permissions the current API token has on a team.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Permissions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_destroy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_api_token: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_member_token_management: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_membership: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_name: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_organization_access: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_sso_team_id: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_visibility: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_view_api_token: Option<bool>,
+}
+/// This struct represents attributes of a team.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_member_token_management: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_access: Option<OrganizationAccess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    /// ID of the team in the SAML identity provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sso_team_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+}
+/// This struct represents relationships of a team.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication_token: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_memberships: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users: Option<Relationship<Many>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing teams of an organization.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of teams per page
+    pub page_size: Option<u32>,
+    /// search by name
+    pub query: Option<String>,
+    /// only return teams with the given names
+    pub names: Vec<String>,
+    /// related resources to include, i.e `users` or
+    /// `organization-memberships`
+    pub include: Vec<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut res = crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+            ("q", self.query.clone()),
+        ]);
+        if !self.names.is_empty() {
+            res.push(("filter[names]".to_string(), self.names.join(",")));
+        }
+        if !self.include.is_empty() {
+            res.push(("include".to_string(), self.include.join(",")));
+        }
+        res
+    }
+}
+/// This struct contains options for creating a team.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    /// name of the team, which must be unique within the organization
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_member_token_management: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_access: Option<OrganizationAccess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sso_team_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+}
+/// This struct contains options for updating a team. Unset fields are left
+/// unchanged.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateOptions {
+    /// new name of the team
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_member_token_management: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization_access: Option<OrganizationAccess>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sso_team_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub visibility: Option<Visibility>,
+}
+// ────────────────────────────────────────────────────────────
+/// Teams implements the teams API.
+#[derive(Debug, Clone)]
+pub struct Teams<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns teams API
+    pub fn teams(&self) -> Teams<'_> {
+        Teams { client: self }
+    }
+}
+impl<'a> Teams<'a> {
+    /// lists teams of an organization
+    pub async fn list(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Team>, Error> {
+        validate_id("organization", organization)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                format!("organizations/{organization}/teams"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns teams of an organization across all pages. The stream ends
+    /// after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<Team, Error>>, Error> {
+        validate_id("organization", organization)?;
+        Ok(self.client.paginate(
+            &format!("organizations/{organization}/teams"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// creates a team in an organization
+    pub async fn create(
+        &self,
+        organization: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        if options.name.is_empty() {
+            return Err(Error::InvalidInput {
+                reason: "team name is required".to_string(),
+            });
+        }
+        let payload = jsonapi::Request::builder("teams")
+            .attributes(options)
+            .build();
+        self.client
+            .post(format!("organizations/{organization}/teams"), None, payload)
+            .await
+    }
+    /// reads a team
+    pub async fn read(&self, team_id: &str) -> Result<Response, Error> {
+        validate_id("team ID", team_id)?;
+        self.client
+            .get(format!("teams/{team_id}"), None, None)
+            .await
+    }
+    /// updates a team
+    pub async fn update(
+        &self,
+        team_id: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("team ID", team_id)?;
+        let payload = jsonapi::Request::builder("teams")
+            .attributes(options)
+            .build();
+        self.client
+            .patch(format!("teams/{team_id}"), None, payload)
+            .await
+    }
+    /// deletes a team
+    pub async fn delete(&self, team_id: &str) -> Result<(), Error> {
+        validate_id("team ID", team_id)?;
+        self.client
+            .delete(format!("teams/{team_id}"), None, ())
+            .await
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "teams::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "teams::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/teams
+        let input = json!({
+            "data": {
+                "id": "team-6p5jTwJQXwqZBncC",
+                "type": "teams",
+                "attributes": {
+                    "name": "team-creation-test",
+                    "sso-team-id": "cb265c8e41bddf3f9926b2cf3d190f0e1627daa4",
+                    "users-count": 0,
+                    "visibility": "organization",
+                    "allow-member-token-management": true,
+                    "permissions": {
+                        "can-update-membership": true,
+                        "can-destroy": true,
+                        "can-update-organization-access": true,
+                        "can-update-api-token": true,
+                        "can-update-visibility": true
+                    },
+                    "organization-access": {
+                        "manage-policies": true,
+                        "manage-policy-overrides": false,
+                        "manage-run-tasks": true,
+                        "manage-workspaces": false,
+                        "manage-vcs-settings": false,
+                        "manage-agent-pools": false,
+                        "manage-projects": false,
+                        "read-projects": false,
+                        "read-workspaces": false
+                    }
+                },
+                "relationships": {
+                    "users": { "data": [] },
+                    "authentication-token": {
+                        "meta": {}
+                    }
+                },
+                "links": { "self": "/api/v2/teams/team-6p5jTwJQXwqZBncC" }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.visibility, Some(Visibility::Organization));
+        assert_eq!(
+            attributes
+                .organization_access
+                .as_ref()
+                .and_then(|v| v.manage_policies),
+            Some(true)
+        );
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,229 @@
+//! This module implements helpers shared by unit tests.
+//!
+//! `Server` is a minimal HTTP server that listens on the loopback interface
+//! and answers requests with canned responses, so that services can be
+//! tested without reaching the API.
+use {
+    std::{
+        collections::HashMap,
+        io::Write,
+        sync::{Arc, Mutex},
+    },
+    tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    },
+};
+// ────────────────────────────────────────────────────────────
+/// This struct represents a canned response of `Server`.
+#[derive(Clone, Debug)]
+pub(crate) struct Reply {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+impl Reply {
+    /// returns a `200 OK` response with a JSON body
+    pub fn json(body: serde_json::Value) -> Self {
+        Self::status(200, body.to_string())
+    }
+    /// returns a response with the given status and body
+    pub fn status(status: u16, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+    /// adds a header to the response
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+}
+/// This struct represents a request received by `Server`.
+#[derive(Clone, Debug)]
+pub(crate) struct Received {
+    pub method: String,
+    /// path and query of the request
+    pub target: String,
+    /// headers with lowercase names
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+/// This struct is a minimal HTTP server that answers requests with the given
+/// responses, in order. Requests that come after the last response are
+/// answered with `500 Internal Server Error`.
+#[derive(Clone, Debug)]
+pub(crate) struct Server {
+    address: String,
+    received: Arc<Mutex<Vec<Received>>>,
+}
+impl Server {
+    /// starts a server on a random port of the loopback interface
+    pub async fn start(replies: Vec<Reply>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let received: Arc<Mutex<Vec<Received>>> = Arc::default();
+        let res = Self {
+            address,
+            received: received.clone(),
+        };
+        tokio::spawn(async move {
+            let mut replies = replies.into_iter();
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let Some(request) = read_request(&mut stream).await else {
+                    continue;
+                };
+                received.lock().unwrap().push(request);
+                let reply = replies.next().unwrap_or_else(|| {
+                    Reply::status(500, "no more canned responses")
+                });
+                write_reply(&mut stream, &reply).await;
+            }
+        });
+        res
+    }
+    /// returns scheme, host and port of the server
+    pub fn address(&self) -> &str {
+        self.address.as_str()
+    }
+    /// returns requests received so far
+    pub fn received(&self) -> Vec<Received> {
+        self.received.lock().unwrap().clone()
+    }
+    /// returns an authenticated API client that sends requests to the server
+    pub fn client(&self) -> crate::Client {
+        crate::client::Builder::new()
+            .set_address(self.address())
+            .unwrap()
+            .set_token("test-token")
+            .unwrap()
+            .build()
+            .unwrap()
+    }
+}
+/// reads a request, decoding chunked bodies
+async fn read_request(stream: &mut TcpStream) -> Option<Received> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let end = loop {
+        if let Some(end) = find(&buffer, b"\r\n\r\n") {
+            break end;
+        }
+        let n = stream.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buffer.extend_from_slice(&chunk[..n]);
+    };
+    let head = String::from_utf8_lossy(&buffer[..end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?.to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(k, v)| (k.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+    let mut body = buffer[end + 4..].to_vec();
+    let chunked = headers
+        .get("transfer-encoding")
+        .map_or(false, |v| v.eq_ignore_ascii_case("chunked"));
+    if chunked {
+        while find(&body, b"0\r\n\r\n").is_none() {
+            let n = stream.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+        body = dechunk(&body);
+    } else {
+        let length = headers
+            .get("content-length")
+            .and_then(|v| v.parse::<usize>().ok())
+            .unwrap_or(0);
+        while body.len() < length {
+            let n = stream.read(&mut chunk).await.ok()?;
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+    }
+    Some(Received {
+        method,
+        target,
+        headers,
+        body,
+    })
+}
+/// writes a response and closes the connection
+async fn write_reply(stream: &mut TcpStream, reply: &Reply) {
+    let mut res = Vec::new();
+    write!(res, "HTTP/1.1 {} Status\r\n", reply.status).unwrap();
+    for (name, value) in &reply.headers {
+        write!(res, "{name}: {value}\r\n").unwrap();
+    }
+    write!(
+        res,
+        "content-length: {}\r\nconnection: close\r\n\r\n{}",
+        reply.body.len(),
+        reply.body
+    )
+    .unwrap();
+    let _ = stream.write_all(&res).await;
+    let _ = stream.shutdown().await;
+}
+/// returns position of the first occurrence of `needle`
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|v| v == needle)
+}
+/// decodes a chunked body
+fn dechunk(mut body: &[u8]) -> Vec<u8> {
+    let mut res = Vec::new();
+    while let Some(end) = find(body, b"\r\n") {
+        let size = String::from_utf8_lossy(&body[..end]);
+        let size = usize::from_str_radix(size.trim(), 16).unwrap_or(0);
+        if size == 0 || body.len() < end + 2 + size {
+            break;
+        }
+        res.extend_from_slice(&body[end + 2..end + 2 + size]);
+        body = &body[(end + 4 + size).min(body.len())..];
+    }
+    res
+}
+// ────────────────────────────────────────────────────────────
+/// This struct collects logs written by a `tracing` subscriber.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct Logs(Arc<Mutex<Vec<u8>>>);
+impl Logs {
+    /// sets a subscriber that records every event, including `TRACE` ones,
+    /// as the default of the current thread until the guard is dropped
+    pub fn capture(&self) -> tracing::subscriber::DefaultGuard {
+        let logs = self.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_max_level(tracing::Level::TRACE)
+            .with_ansi(false)
+            .with_writer(move || logs.clone())
+            .finish();
+        tracing::subscriber::set_default(subscriber)
+    }
+    /// returns logs collected so far
+    pub fn contents(&self) -> String {
+        String::from_utf8_lossy(&self.0.lock().unwrap()).to_string()
+    }
+}
+impl Write for Logs {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,61 @@
+//! This module implements the audit trail tokens API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/audit-trails-tokens).
+//!
+//! Audit trail tokens can only read the audit trail of their organization.
+//! They share the endpoint of organization tokens, and are selected with the
+//! `token=audit-trails` query parameter.
+use {
+    super::{create_request, CreateOptions, Response},
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+    },
+};
+// ────────────────────────────────────────────────────────────
+/// returns the path of the audit trail token of an organization
+fn path(organization: &str) -> String {
+    format!(
+        "organizations/{organization}/authentication-token?token=audit-trails"
+    )
+}
+// ────────────────────────────────────────────────────────────
+/// AuditTrailTokens implements the audit trail tokens API.
+#[derive(Debug, Clone)]
+pub struct AuditTrailTokens<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns audit trail tokens API
+    pub fn audit_trail_tokens(&self) -> AuditTrailTokens<'_> {
+        AuditTrailTokens { client: self }
+    }
+}
+impl<'a> AuditTrailTokens<'a> {
+    /// creates the audit trail token of an organization, replacing the
+    /// existing one
+    pub async fn create(
+        &self,
+        organization: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        self.client
+            .post(path(organization), None, create_request(options))
+            .await
+    }
+    /// reads the audit trail token of an organization
+    pub async fn read(&self, organization: &str) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        self.client.get(path(organization), None, None).await
+    }
+    /// deletes the audit trail token of an organization
+    pub async fn delete(&self, organization: &str) -> Result<(), Error> {
+        validate_id("organization", organization)?;
+        self.client.delete(path(organization), None, ()).await
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
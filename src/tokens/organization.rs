@@ -0,0 +1,68 @@
+//! This module implements the organization tokens API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/organization-tokens).
+//!
+//! An organization has a single token, which is replaced whenever a new one
+//! is created.
+use {
+    super::{create_request, CreateOptions, Response},
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+    },
+};
+// ────────────────────────────────────────────────────────────
+/// OrganizationTokens implements the organization tokens API.
+#[derive(Debug, Clone)]
+pub struct OrganizationTokens<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns organization tokens API
+    pub fn organization_tokens(&self) -> OrganizationTokens<'_> {
+        OrganizationTokens { client: self }
+    }
+}
+impl<'a> OrganizationTokens<'a> {
+    /// creates the token of an organization, replacing the existing one
+    pub async fn create(
+        &self,
+        organization: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        self.client
+            .post(
+                format!("organizations/{organization}/authentication-token"),
+                None,
+                create_request(options),
+            )
+            .await
+    }
+    /// reads the token of an organization
+    pub async fn read(&self, organization: &str) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        self.client
+            .get(
+                format!("organizations/{organization}/authentication-token"),
+                None,
+                None,
+            )
+            .await
+    }
+    /// deletes the token of an organization
+    pub async fn delete(&self, organization: &str) -> Result<(), Error> {
+        validate_id("organization", organization)?;
+        self.client
+            .delete(
+                format!("organizations/{organization}/authentication-token"),
+                None,
+                (),
+            )
+            .await
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,160 @@
+//! This module implements the team tokens API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/team-tokens).
+//!
+//! A team has either a single unnamed token, which is replaced whenever a
+//! new one is created, or any number of tokens that are told apart by their
+//! description.
+use {
+    super::{
+        create_request, Attributes, CreateOptions, ListOptions, Relationships,
+        Response, Token,
+    },
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi,
+        pagination::PaginatorOptions,
+    },
+    futures::Stream,
+};
+// ────────────────────────────────────────────────────────────
+/// TeamTokens implements the team tokens API.
+#[derive(Debug, Clone)]
+pub struct TeamTokens<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns team tokens API
+    pub fn team_tokens(&self) -> TeamTokens<'_> {
+        TeamTokens { client: self }
+    }
+}
+impl<'a> TeamTokens<'a> {
+    /// creates the unnamed token of a team, replacing the existing one
+    pub async fn create(
+        &self,
+        team_id: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("team ID", team_id)?;
+        self.client
+            .post(
+                format!("teams/{team_id}/authentication-token"),
+                None,
+                create_request(options),
+            )
+            .await
+    }
+    /// reads the unnamed token of a team
+    pub async fn read(&self, team_id: &str) -> Result<Response, Error> {
+        validate_id("team ID", team_id)?;
+        self.client
+            .get(format!("teams/{team_id}/authentication-token"), None, None)
+            .await
+    }
+    /// deletes the unnamed token of a team
+    pub async fn delete(&self, team_id: &str) -> Result<(), Error> {
+        validate_id("team ID", team_id)?;
+        self.client
+            .delete(format!("teams/{team_id}/authentication-token"), None, ())
+            .await
+    }
+    /// lists the named tokens of a team
+    pub async fn list(
+        &self,
+        team_id: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Token>, Error> {
+        validate_id("team ID", team_id)?;
+        self.list_path(
+            format!("teams/{team_id}/authentication-tokens"),
+            options,
+        )
+        .await
+    }
+    /// returns the named tokens of a team across all pages. The stream ends
+    /// after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        team_id: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<Token, Error>>, Error> {
+        validate_id("team ID", team_id)?;
+        Ok(self.client.paginate(
+            &format!("teams/{team_id}/authentication-tokens"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// lists the tokens of all teams of an organization
+    pub async fn list_for_organization(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Token>, Error> {
+        validate_id("organization", organization)?;
+        self.list_path(
+            format!("organizations/{organization}/team-tokens"),
+            options,
+        )
+        .await
+    }
+    /// creates a named token for a team, next to its existing tokens
+    pub async fn create_named(
+        &self,
+        team_id: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("team ID", team_id)?;
+        if options
+            .description
+            .as_deref()
+            .unwrap_or_default()
+            .is_empty()
+        {
+            return Err(Error::InvalidInput {
+                reason: "token description is required".to_string(),
+            });
+        }
+        self.client
+            .post(
+                format!("teams/{team_id}/authentication-tokens"),
+                None,
+                create_request(options),
+            )
+            .await
+    }
+    /// reads a team token by its ID
+    pub async fn read_by_id(&self, token_id: &str) -> Result<Response, Error> {
+        validate_id("token ID", token_id)?;
+        self.client
+            .get(format!("authentication-tokens/{token_id}"), None, None)
+            .await
+    }
+    /// deletes a team token by its ID
+    pub async fn delete_by_id(&self, token_id: &str) -> Result<(), Error> {
+        validate_id("token ID", token_id)?;
+        self.client
+            .delete(format!("authentication-tokens/{token_id}"), None, ())
+            .await
+    }
+    /// sends a request to a list endpoint of team tokens
+    async fn list_path(
+        &self,
+        path: String,
+        options: &ListOptions,
+    ) -> Result<Vec<Token>, Error> {
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(path, None, Some(&crate::core::query(&query)))
+            .await?;
+        Ok(response.into_data())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,97 @@
+//! This module implements the user tokens API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/user-tokens).
+use {
+    super::{
+        create_request, Attributes, CreateOptions, ListOptions, Relationships,
+        Response, Token,
+    },
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi,
+        pagination::PaginatorOptions,
+    },
+    futures::Stream,
+};
+// ────────────────────────────────────────────────────────────
+/// UserTokens implements the user tokens API.
+#[derive(Debug, Clone)]
+pub struct UserTokens<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns user tokens API
+    pub fn user_tokens(&self) -> UserTokens<'_> {
+        UserTokens { client: self }
+    }
+}
+impl<'a> UserTokens<'a> {
+    /// lists tokens of a user
+    pub async fn list(
+        &self,
+        user_id: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Token>, Error> {
+        validate_id("user ID", user_id)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                format!("users/{user_id}/authentication-tokens"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns tokens of a user across all pages. The stream ends after the
+    /// last page or after the first error.
+    pub fn list_all(
+        &self,
+        user_id: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<Token, Error>>, Error> {
+        validate_id("user ID", user_id)?;
+        Ok(self.client.paginate(
+            &format!("users/{user_id}/authentication-tokens"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// creates a token for a user. The response is the only place the token
+    /// can be read from.
+    pub async fn create(
+        &self,
+        user_id: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("user ID", user_id)?;
+        self.client
+            .post(
+                format!("users/{user_id}/authentication-tokens"),
+                None,
+                create_request(options),
+            )
+            .await
+    }
+    /// reads a user token
+    pub async fn read(&self, token_id: &str) -> Result<Response, Error> {
+        validate_id("token ID", token_id)?;
+        self.client
+            .get(format!("authentication-tokens/{token_id}"), None, None)
+            .await
+    }
+    /// deletes a user token
+    pub async fn delete(&self, token_id: &str) -> Result<(), Error> {
+        validate_id("token ID", token_id)?;
+        self.client
+            .delete(format!("authentication-tokens/{token_id}"), None, ())
+            .await
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,176 @@
+//! This module implements the users and account API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/users)
+//! and
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/account).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let user = client.users().read_current().await.unwrap().into_data();
+//!     println!("{:?}", user.get_attributes());
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, Relationship},
+    },
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a user resource.
+pub type User = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single user.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This struct represents two factor authentication settings of a user.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct TwoFactor {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub verified: bool,
+}
+/// This struct represents permissions the current API token has on a user.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Permissions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_change_email: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_change_username: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_create_organizations: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_manage_user_tokens: Option<bool>,
+}
+/// This struct represents attributes of a user.
+///
+/// NOTE: the email address is only returned for the current user and for
+/// members of organizations the current user administers.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_service_account: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_site_admin: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub two_factor: Option<TwoFactor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unconfirmed_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v2_only: Option<bool>,
+}
+/// This struct represents relationships of a user.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authentication_tokens: Option<Relationship<Many>>,
+}
+// ────────────────────────────────────────────────────────────
+/// Users implements the users and account API.
+#[derive(Debug, Clone)]
+pub struct Users<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns users API
+    pub fn users(&self) -> Users<'_> {
+        Users { client: self }
+    }
+}
+impl<'a> Users<'a> {
+    /// reads a user
+    pub async fn read(&self, user_id: &str) -> Result<Response, Error> {
+        validate_id("user ID", user_id)?;
+        self.client
+            .get(format!("users/{user_id}"), None, None)
+            .await
+    }
+    /// reads the user the API token belongs to
+    pub async fn read_current(&self) -> Result<Response, Error> {
+        self.client
+            .get("account/details".to_string(), None, None)
+            .await
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "users::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "users::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/account
+        let input = json!({
+            "data": {
+                "id": "user-V3R563qtJNcExAkN",
+                "type": "users",
+                "attributes": {
+                    "username": "admin",
+                    "is-service-account": false,
+                    "avatar-url": "https://www.gravatar.com/avatar/9babb00091b97b9ce9538c45807fd35f?s=100&d=mm",
+                    "v2-only": false,
+                    "is-site-admin": true,
+                    "is-sso-login": false,
+                    "email": "admin@hashicorp.com",
+                    "unconfirmed-email": null,
+                    "permissions": {
+                        "can-create-organizations": true,
+                        "can-change-email": true,
+                        "can-change-username": true
+                    }
+                },
+                "relationships": {
+                    "authentication-tokens": {
+                        "links": {
+                            "related": "/api/v2/users/user-V3R563qtJNcExAkN/authentication-tokens"
+                        }
+                    }
+                },
+                "links": { "self": "/api/v2/users/user-V3R563qtJNcExAkN" }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.username, "admin");
+        assert_eq!(attributes.email.as_deref(), Some("admin@hashicorp.com"));
+        assert_eq!(attributes.is_site_admin, Some(true));
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,703 @@
+//! This module implements the variable sets API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/variable-sets).
+//!
+//! Variables of a variable set use the types of `crate::variables`, so their
+//! values are held in `crate::core::Secret` as well.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = tfe_oxide::variable_sets::CreateOptions {
+//!         name: "aws-credentials".to_string(),
+//!         workspace_ids: vec!["ws-4j8p6jX1w33MiDC7".to_string()],
+//!         ..Default::default()
+//!     };
+//!     let varset = client
+//!         .variable_sets()
+//!         .create("my-organization", &options)
+//!         .await
+//!         .unwrap();
+//!     println!("{:?}", varset.get_data().get_id());
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, One, Relationship},
+        pagination::PaginatorOptions,
+        variables,
+    },
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a variable set resource.
+pub type VariableSet = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single variable set.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This struct represents attributes of a variable set.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// true if the variable set applies to all workspaces of the
+    /// organization
+    #[serde(default)]
+    pub global: bool,
+    /// true if variables of the set override variables that are defined
+    /// elsewhere, e.g in a workspace or with the CLI
+    #[serde(default)]
+    pub priority: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub var_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspace_count: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_count: Option<u32>,
+}
+/// This struct represents relationships of a variable set.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projects: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vars: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workspaces: Option<Relationship<Many>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing variable sets.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of variable sets per page
+    pub page_size: Option<u32>,
+    /// search by name
+    pub query: Option<String>,
+    /// related resources to include, e.g `vars`
+    pub include: Vec<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut res = crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+            ("q", self.query.clone()),
+        ]);
+        if !self.include.is_empty() {
+            res.push(("include".to_string(), self.include.join(",")));
+        }
+        res
+    }
+}
+/// This struct contains options for reading a variable set.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// related resources to include, i.e `vars`, `workspaces` or `projects`
+    pub include: Vec<String>,
+}
+impl ReadOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        if self.include.is_empty() {
+            return Vec::new();
+        }
+        vec![("include".to_string(), self.include.join(","))]
+    }
+}
+/// This struct contains options for creating a variable set.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// applies the variable set to all workspaces of the organization
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global: Option<bool>,
+    /// makes variables of the set override other variables
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<bool>,
+    /// workspaces the variable set is applied to
+    #[serde(skip)]
+    pub workspace_ids: Vec<String>,
+    /// projects the variable set is applied to
+    #[serde(skip)]
+    pub project_ids: Vec<String>,
+}
+/// This struct contains options for updating a variable set. Unset fields
+/// are left unchanged.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<bool>,
+}
+// ────────────────────────────────────────────────────────────
+/// returns request document for creating a variable set. Relationships
+/// are left out when they are empty.
+fn create_request(
+    options: &CreateOptions,
+) -> jsonapi::Request<&CreateOptions> {
+    let mut request = jsonapi::Request::builder("varsets").attributes(options);
+    if !options.workspace_ids.is_empty() {
+        request = request.to_many(
+            "workspaces",
+            "workspaces",
+            &options.workspace_ids,
+        );
+    }
+    if !options.project_ids.is_empty() {
+        request =
+            request.to_many("projects", "projects", &options.project_ids);
+    }
+    request.build()
+}
+// ────────────────────────────────────────────────────────────
+/// VariableSets implements the variable sets API.
+#[derive(Debug, Clone)]
+pub struct VariableSets<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns variable sets API
+    pub fn variable_sets(&self) -> VariableSets<'_> {
+        VariableSets { client: self }
+    }
+}
+impl<'a> VariableSets<'a> {
+    /// lists variable sets of an organization
+    pub async fn list(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<VariableSet>, Error> {
+        validate_id("organization", organization)?;
+        self.list_path(
+            format!("organizations/{organization}/varsets"),
+            options,
+        )
+        .await
+    }
+    /// returns variable sets of an organization across all pages. The stream
+    /// ends after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<VariableSet, Error>>, Error> {
+        validate_id("organization", organization)?;
+        Ok(self.client.paginate(
+            &format!("organizations/{organization}/varsets"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// lists variable sets that are applied to a workspace
+    pub async fn list_for_workspace(
+        &self,
+        workspace_id: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<VariableSet>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.list_path(format!("workspaces/{workspace_id}/varsets"), options)
+            .await
+    }
+    /// lists variable sets that are applied to a project
+    pub async fn list_for_project(
+        &self,
+        project_id: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<VariableSet>, Error> {
+        validate_id("project ID", project_id)?;
+        self.list_path(format!("projects/{project_id}/varsets"), options)
+            .await
+    }
+    /// creates a variable set in an organization
+    pub async fn create(
+        &self,
+        organization: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        for id in &options.workspace_ids {
+            validate_id("workspace ID", id)?;
+        }
+        for id in &options.project_ids {
+            validate_id("project ID", id)?;
+        }
+        self.client
+            .post(
+                format!("organizations/{organization}/varsets"),
+                None,
+                create_request(options),
+            )
+            .await
+    }
+    /// reads a variable set
+    pub async fn read(
+        &self,
+        varset_id: &str,
+        options: &ReadOptions,
+    ) -> Result<Response, Error> {
+        validate_id("variable set ID", varset_id)?;
+        let query = options.to_query();
+        self.client
+            .get(
+                format!("varsets/{varset_id}"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await
+    }
+    /// updates a variable set
+    pub async fn update(
+        &self,
+        varset_id: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("variable set ID", varset_id)?;
+        let payload = jsonapi::Request::builder("varsets")
+            .id(varset_id)
+            .attributes(options)
+            .build();
+        self.client
+            .patch(format!("varsets/{varset_id}"), None, payload)
+            .await
+    }
+    /// deletes a variable set
+    pub async fn delete(&self, varset_id: &str) -> Result<(), Error> {
+        validate_id("variable set ID", varset_id)?;
+        self.client
+            .delete(format!("varsets/{varset_id}"), None, ())
+            .await
+    }
+    /// applies a variable set to workspaces
+    pub async fn apply_to_workspaces(
+        &self,
+        varset_id: &str,
+        workspace_ids: &[&str],
+    ) -> Result<(), Error> {
+        self.relationship("workspaces", varset_id, workspace_ids, true)
+            .await
+    }
+    /// removes a variable set from workspaces
+    pub async fn remove_from_workspaces(
+        &self,
+        varset_id: &str,
+        workspace_ids: &[&str],
+    ) -> Result<(), Error> {
+        self.relationship("workspaces", varset_id, workspace_ids, false)
+            .await
+    }
+    /// applies a variable set to projects
+    pub async fn apply_to_projects(
+        &self,
+        varset_id: &str,
+        project_ids: &[&str],
+    ) -> Result<(), Error> {
+        self.relationship("projects", varset_id, project_ids, true)
+            .await
+    }
+    /// removes a variable set from projects
+    pub async fn remove_from_projects(
+        &self,
+        varset_id: &str,
+        project_ids: &[&str],
+    ) -> Result<(), Error> {
+        self.relationship("projects", varset_id, project_ids, false)
+            .await
+    }
+    /// lists variables of a variable set
+    pub async fn list_variables(
+        &self,
+        varset_id: &str,
+    ) -> Result<Vec<variables::Variable>, Error> {
+        validate_id("variable set ID", varset_id)?;
+        let response: jsonapi::SuccessList<
+            variables::Attributes,
+            variables::Relationships,
+        > = self
+            .client
+            .get(
+                format!("varsets/{varset_id}/relationships/vars"),
+                None,
+                None,
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// creates a variable in a variable set
+    pub async fn create_variable(
+        &self,
+        varset_id: &str,
+        options: &variables::CreateOptions,
+    ) -> Result<variables::Response, Error> {
+        validate_id("variable set ID", varset_id)?;
+        self.client
+            .post(
+                format!("varsets/{varset_id}/relationships/vars"),
+                None,
+                variables::request(None, options),
+            )
+            .await
+    }
+    /// reads a variable of a variable set
+    pub async fn read_variable(
+        &self,
+        varset_id: &str,
+        variable_id: &str,
+    ) -> Result<variables::Response, Error> {
+        validate_id("variable set ID", varset_id)?;
+        validate_id("variable ID", variable_id)?;
+        self.client
+            .get(
+                format!(
+                    "varsets/{varset_id}/relationships/vars/{variable_id}"
+                ),
+                None,
+                None,
+            )
+            .await
+    }
+    /// updates a variable of a variable set
+    pub async fn update_variable(
+        &self,
+        varset_id: &str,
+        variable_id: &str,
+        options: &variables::UpdateOptions,
+    ) -> Result<variables::Response, Error> {
+        validate_id("variable set ID", varset_id)?;
+        validate_id("variable ID", variable_id)?;
+        self.client
+            .patch(
+                format!(
+                    "varsets/{varset_id}/relationships/vars/{variable_id}"
+                ),
+                None,
+                variables::request(Some(variable_id), options),
+            )
+            .await
+    }
+    /// deletes a variable of a variable set
+    pub async fn delete_variable(
+        &self,
+        varset_id: &str,
+        variable_id: &str,
+    ) -> Result<(), Error> {
+        validate_id("variable set ID", varset_id)?;
+        validate_id("variable ID", variable_id)?;
+        self.client
+            .delete(
+                format!(
+                    "varsets/{varset_id}/relationships/vars/{variable_id}"
+                ),
+                None,
+                (),
+            )
+            .await
+    }
+    /// sends a request to a list endpoint of variable sets
+    async fn list_path(
+        &self,
+        path: String,
+        options: &ListOptions,
+    ) -> Result<Vec<VariableSet>, Error> {
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(path, None, Some(&crate::core::query(&query)))
+            .await?;
+        Ok(response.into_data())
+    }
+    /// adds resources to, or removes them from, a to-many relationship of a
+    /// variable set
+    async fn relationship(
+        &self,
+        relationship: &str,
+        varset_id: &str,
+        ids: &[&str],
+        add: bool,
+    ) -> Result<(), Error> {
+        validate_id("variable set ID", varset_id)?;
+        for id in ids {
+            validate_id("resource ID", id)?;
+        }
+        let path = format!("varsets/{varset_id}/relationships/{relationship}");
+        let payload = Relationship::many(relationship, ids);
+        match add {
+            true => self.client.post(path, None, payload).await,
+            false => self.client.delete(path, None, payload).await,
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "variable_sets::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "variable_sets::tests" --nocapture'
+    use {
+        super::*,
+        crate::testing::{Reply, Server},
+        serde_json::json,
+    };
+    /// returns JSON body of a received request
+    fn body(received: &crate::testing::Received) -> serde_json::Value {
+        serde_json::from_slice(&received.body).unwrap()
+    }
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/variable-sets
+        let input = json!({
+            "data": {
+                "id": "varset-kjkN545LH2Sfercv",
+                "type": "varsets",
+                "attributes": {
+                    "name": "MyVarset",
+                    "description": "Full of vars and such for mass reuse",
+                    "global": false,
+                    "priority": true,
+                    "updated-at": "2023-03-06T21:48:33.588Z",
+                    "var-count": 1,
+                    "workspace-count": 1,
+                    "project-count": 0
+                },
+                "relationships": {
+                    "organization": {
+                        "data": { "id": "org-Lbnwx4uy6qxgAHF1", "type": "organizations" }
+                    },
+                    "vars": {
+                        "data": [{ "id": "var-mMqadSCxZtrQJAv8", "type": "vars" }]
+                    },
+                    "workspaces": {
+                        "data": [{ "id": "ws-UohFdKAHUGsQ8Dtf", "type": "workspaces" }]
+                    },
+                    "projects": { "data": [] }
+                }
+            },
+            "included": [
+                {
+                    "id": "var-mMqadSCxZtrQJAv8",
+                    "type": "vars",
+                    "attributes": {
+                        "key": "c2e4612d993c18e42ef30405ea7d0e9ae",
+                        "value": "8676328808c5bf56ac1c8c8c6eeed0ab",
+                        "sensitive": false,
+                        "category": "terraform",
+                        "hcl": false
+                    }
+                }
+            ]
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.name, "MyVarset");
+        assert!(attributes.priority);
+        assert!(!attributes.global);
+        assert_eq!(actual.get_included().len(), 1);
+        let relationships = actual.get_data().relationships.as_ref().unwrap();
+        let vars: Vec<variables::Variable> = actual
+            .get_included()
+            .resolve_many(relationships.vars.as_ref().unwrap())?;
+        assert_eq!(vars[0].get_id().as_deref(), Some("var-mMqadSCxZtrQJAv8"));
+        Ok(())
+    }
+    #[test]
+    fn test_create_request() {
+        let options = CreateOptions {
+            name: "aws".to_string(),
+            global: Some(false),
+            workspace_ids: vec!["ws-123".to_string()],
+            ..Default::default()
+        };
+        let expected = json!({
+            "data": {
+                "type": "varsets",
+                "attributes": { "name": "aws", "global": false },
+                "relationships": {
+                    "workspaces": {
+                        "data": [{ "type": "workspaces", "id": "ws-123" }]
+                    }
+                }
+            }
+        });
+        assert_eq!(
+            serde_json::to_value(create_request(&options)).unwrap(),
+            expected
+        );
+        let options = CreateOptions {
+            name: "aws".to_string(),
+            ..Default::default()
+        };
+        let expected = json!({
+            "data": { "type": "varsets", "attributes": { "name": "aws" } }
+        });
+        assert_eq!(
+            serde_json::to_value(create_request(&options)).unwrap(),
+            expected
+        );
+    }
+    #[tokio::test]
+    async fn test_apply_and_remove() {
+        let server = Server::start(vec![Reply::status(204, ""); 4]).await;
+        let client = server.client();
+        let varsets = client.variable_sets();
+        varsets
+            .apply_to_workspaces("varset-123", &["ws-1", "ws-2"])
+            .await
+            .unwrap();
+        varsets
+            .remove_from_workspaces("varset-123", &["ws-1"])
+            .await
+            .unwrap();
+        varsets
+            .apply_to_projects("varset-123", &["prj-1"])
+            .await
+            .unwrap();
+        varsets
+            .remove_from_projects("varset-123", &["prj-1"])
+            .await
+            .unwrap();
+        let received = server.received();
+        let actual: Vec<(&str, &str)> = received
+            .iter()
+            .map(|v| (v.method.as_str(), v.target.as_str()))
+            .collect();
+        assert_eq!(
+            actual,
+            vec![
+                (
+                    "POST",
+                    "/api/v2/varsets/varset-123/relationships/workspaces"
+                ),
+                (
+                    "DELETE",
+                    "/api/v2/varsets/varset-123/relationships/workspaces"
+                ),
+                ("POST", "/api/v2/varsets/varset-123/relationships/projects"),
+                (
+                    "DELETE",
+                    "/api/v2/varsets/varset-123/relationships/projects"
+                ),
+            ]
+        );
+        assert_eq!(
+            body(&received[0]),
+            json!({
+                "data": [
+                    { "type": "workspaces", "id": "ws-1" },
+                    { "type": "workspaces", "id": "ws-2" }
+                ]
+            })
+        );
+        assert_eq!(
+            body(&received[1]),
+            json!({ "data": [{ "type": "workspaces", "id": "ws-1" }] })
+        );
+        assert_eq!(
+            body(&received[2]),
+            json!({ "data": [{ "type": "projects", "id": "prj-1" }] })
+        );
+        assert_eq!(body(&received[3]), body(&received[2]));
+    }
+    #[tokio::test]
+    async fn test_variables() {
+        let server = Server::start(vec![
+            Reply::json(json!({
+                "data": {
+                    "id": "var-123",
+                    "type": "vars",
+                    "attributes": {
+                        "key": "region",
+                        "value": "eu-west-1",
+                        "category": "env"
+                    }
+                }
+            })),
+            Reply::status(204, ""),
+        ])
+        .await;
+        let client = server.client();
+        let options = variables::CreateOptions {
+            key: "region".to_string(),
+            value: Some("eu-west-1".into()),
+            category: variables::Category::Env,
+            ..Default::default()
+        };
+        let actual = client
+            .variable_sets()
+            .create_variable("varset-123", &options)
+            .await
+            .unwrap();
+        assert_eq!(actual.get_data().get_id().as_deref(), Some("var-123"));
+        client
+            .variable_sets()
+            .delete_variable("varset-123", "var-123")
+            .await
+            .unwrap();
+        let received = server.received();
+        assert_eq!(received[0].method, "POST");
+        assert_eq!(
+            received[0].target,
+            "/api/v2/varsets/varset-123/relationships/vars"
+        );
+        assert_eq!(
+            body(&received[0]),
+            json!({
+                "data": {
+                    "type": "vars",
+                    "attributes": {
+                        "key": "region",
+                        "value": "eu-west-1",
+                        "category": "env"
+                    }
+                }
+            })
+        );
+        assert_eq!(received[1].method, "DELETE");
+        assert_eq!(
+            received[1].target,
+            "/api/v2/varsets/varset-123/relationships/vars/var-123"
+        );
+        assert!(received[1].body.is_empty());
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,306 @@
+//! This module implements the workspace variables API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/workspace-variables).
+//!
+//! Values are held in `crate::core::Secret`, so they are wiped from memory
+//! when dropped and never show up in `Debug` output or request traces.
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let options = tfe_oxide::variables::CreateOptions {
+//!         key: "db_password".to_string(),
+//!         value: Some("hunter2".into()),
+//!         sensitive: Some(true),
+//!         ..Default::default()
+//!     };
+//!     let variable = client
+//!         .variables()
+//!         .create("ws-4j8p6jX1w33MiDC7", &options)
+//!         .await
+//!         .unwrap();
+//!     println!("{:?}", variable.get_data().get_id());
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient, Secret},
+        jsonapi::{self, One, Relationship},
+    },
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a workspace variable resource.
+pub type Variable = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single variable.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This enum represents the kind of a variable.
+#[derive(
+    PartialEq, Eq, Hash, Debug, Clone, Copy, Default, Deserialize, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Category {
+    /// Terraform input variable
+    #[default]
+    Terraform,
+    /// environment variable of Terraform runs
+    Env,
+}
+/// This struct represents attributes of a variable.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub key: String,
+    /// value of the variable. It is never returned for sensitive variables.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub category: Category,
+    /// true if the value is parsed as HCL, e.g a list or a map
+    #[serde(default)]
+    pub hcl: bool,
+    /// true if the value is write-only
+    #[serde(default)]
+    pub sensitive: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+}
+/// This struct represents relationships of a variable.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configurable: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for creating a variable.
+#[derive(PartialEq, Eq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    pub key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub category: Category,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hcl: Option<bool>,
+    /// makes the variable write-only. A sensitive variable can not be made
+    /// non-sensitive.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<bool>,
+}
+/// This struct contains options for updating a variable. Unset fields are
+/// left unchanged.
+#[derive(PartialEq, Eq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Secret>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<Category>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hcl: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<bool>,
+}
+/// returns a create or update request document. Values are kept as
+/// `Secret`, so the request trace does not reveal them.
+pub(crate) fn request<'a, T>(
+    id: Option<&str>,
+    attributes: &'a T,
+) -> jsonapi::Request<&'a T> {
+    let mut request = jsonapi::Request::builder("vars").attributes(attributes);
+    if let Some(id) = id {
+        request = request.id(id);
+    }
+    request.build()
+}
+// ────────────────────────────────────────────────────────────
+/// Variables implements the workspace variables API.
+#[derive(Debug, Clone)]
+pub struct Variables<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns workspace variables API
+    pub fn variables(&self) -> Variables<'_> {
+        Variables { client: self }
+    }
+}
+impl<'a> Variables<'a> {
+    /// lists variables of a workspace
+    pub async fn list(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Vec<Variable>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(format!("workspaces/{workspace_id}/vars"), None, None)
+            .await?;
+        Ok(response.into_data())
+    }
+    /// creates a variable in a workspace
+    pub async fn create(
+        &self,
+        workspace_id: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        if options.key.is_empty() {
+            return Err(Error::InvalidInput {
+                reason: "variable key is required".to_string(),
+            });
+        }
+        self.client
+            .post(
+                format!("workspaces/{workspace_id}/vars"),
+                None,
+                request(None, options),
+            )
+            .await
+    }
+    /// reads a variable of a workspace
+    pub async fn read(
+        &self,
+        workspace_id: &str,
+        variable_id: &str,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        validate_id("variable ID", variable_id)?;
+        self.client
+            .get(
+                format!("workspaces/{workspace_id}/vars/{variable_id}"),
+                None,
+                None,
+            )
+            .await
+    }
+    /// updates a variable of a workspace
+    pub async fn update(
+        &self,
+        workspace_id: &str,
+        variable_id: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        validate_id("variable ID", variable_id)?;
+        self.client
+            .patch(
+                format!("workspaces/{workspace_id}/vars/{variable_id}"),
+                None,
+                request(Some(variable_id), options),
+            )
+            .await
+    }
+    /// deletes a variable of a workspace
+    pub async fn delete(
+        &self,
+        workspace_id: &str,
+        variable_id: &str,
+    ) -> Result<(), Error> {
+        validate_id("workspace ID", workspace_id)?;
+        validate_id("variable ID", variable_id)?;
+        self.client
+            .delete(
+                format!("workspaces/{workspace_id}/vars/{variable_id}"),
+                None,
+                (),
+            )
+            .await
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "variables::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "variables::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/workspace-variables
+        let input = json!({
+            "data": {
+                "id": "var-EavQ1LztoRTQHSNT",
+                "type": "vars",
+                "attributes": {
+                    "key": "some_key",
+                    "value": "some_value",
+                    "description": "some description",
+                    "sensitive": false,
+                    "category": "terraform",
+                    "hcl": false,
+                    "version-id": "1aa07d63ea8ff4df941c94ca9ac2f6fb",
+                    "created-at": "2021-08-12T23:24:29.211Z"
+                },
+                "relationships": {
+                    "configurable": {
+                        "data": { "id": "ws-cZE9LERN3rGPRAmH", "type": "workspaces" },
+                        "links": { "related": "/api/v2/workspaces/ws-cZE9LERN3rGPRAmH" }
+                    }
+                },
+                "links": {
+                    "self": "/api/v2/workspaces/ws-cZE9LERN3rGPRAmH/vars/var-EavQ1LztoRTQHSNT"
+                }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let attributes = actual.get_data().attributes.as_ref().unwrap();
+        assert_eq!(attributes.category, Category::Terraform);
+        assert_eq!(
+            attributes.value.as_ref().map(Secret::expose),
+            Some("some_value")
+        );
+        assert!(!format!("{attributes:?}").contains("some_value"));
+        Ok(())
+    }
+    #[test]
+    fn test_request() -> Result<(), serde_json::Error> {
+        let options = CreateOptions {
+            key: "AWS_SECRET_ACCESS_KEY".to_string(),
+            value: Some("hunter2".into()),
+            category: Category::Env,
+            sensitive: Some(true),
+            ..Default::default()
+        };
+        let actual = request(None, &options);
+        assert!(!format!("{actual:?}").contains("hunter2"));
+        let expected = json!({
+            "data": {
+                "type": "vars",
+                "attributes": {
+                    "key": "AWS_SECRET_ACCESS_KEY",
+                    "value": "hunter2",
+                    "category": "env",
+                    "sensitive": true
+                }
+            }
+        });
+        assert_eq!(serde_json::to_value(&actual)?, expected);
+        Ok(())
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4
@@ -0,0 +1,874 @@
+//! This module implements the workspaces API.
+//!
+//! API documentation is available at
+//! [developer.hashicorp.com](https://developer.hashicorp.com/terraform/cloud-docs/api-docs/workspaces).
+//!
+//! # Examples
+//!
+//! Basic usage:
+//!
+//! ```no_run
+//! #[tokio::main]
+//! async fn main() {
+//!     let client = tfe_oxide::Client::new("my-api-token").unwrap();
+//!     let workspace = client
+//!         .workspaces()
+//!         .read("my-organization", "my-workspace", &Default::default())
+//!         .await
+//!         .unwrap();
+//!     println!("{:?}", workspace.get_data().get_id());
+//! }
+//! ```
+use {
+    crate::{
+        client::{validate_id, Client},
+        core::{Error, HttpClient},
+        jsonapi::{self, Many, One, Relationship},
+        pagination::PaginatorOptions,
+    },
+    futures::Stream,
+    getset::Getters,
+    serde::{Deserialize, Serialize},
+    serde_json::json,
+};
+// ────────────────────────────────────────────────────────────
+/// This represents a workspace resource.
+pub type Workspace = jsonapi::Data<Attributes, Relationships>;
+/// This represents a server response that contains a single workspace.
+pub type Response = jsonapi::Success<Attributes, Relationships>;
+// ────────────────────────────────────────────────────────────
+/// This struct represents actions that can be performed on a workspace.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Actions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_destroyable: Option<bool>,
+}
+/// This struct represents permissions the current API token has on a
+/// workspace.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Permissions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_destroy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_force_unlock: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_lock: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_manage_run_tasks: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_queue_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_queue_destroy: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_queue_run: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_read_settings: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_unlock: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub can_update_variable: Option<bool>,
+}
+/// This struct represents the VCS repository a workspace is connected to.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct VcsRepo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_identifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingress_submodules: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oauth_token_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub github_app_installation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub repository_http_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags_regex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_url: Option<String>,
+}
+/// This struct represents attributes of a workspace.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Attributes {
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actions: Option<Actions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_destroy_plan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub apply_duration_average: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assessments_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_apply_run_trigger: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_destroy_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_triggers_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_remote_state: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_change_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operations: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Permissions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plan_duration_average: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy_check_failures: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_all_runs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource_count: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_failures: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speculative_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structured_run_output_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_names: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_patterns: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_prefixes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_repo: Option<VcsRepo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+}
+/// This struct represents relationships of a workspace.
+#[derive(
+    PartialEq, Debug, Clone, Default, Deserialize, Serialize, Getters,
+)]
+#[getset(get = "pub with_prefix")]
+#[serde(rename_all = "kebab-case")]
+pub struct Relationships {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_pool: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_configuration_version: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_run: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_state_version: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub latest_run: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locked_by: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outputs: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub readme: Option<Relationship<One>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remote_state_consumers: Option<Relationship<Many>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_key: Option<Relationship<One>>,
+}
+// ────────────────────────────────────────────────────────────
+/// This struct contains options for listing workspaces of an organization.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ListOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of workspaces per page
+    pub page_size: Option<u32>,
+    /// fuzzy search by workspace name
+    pub search: Option<String>,
+    /// comma-separated list of tags that workspaces must have
+    pub tags: Option<String>,
+    /// comma-separated list of tags that workspaces must not have
+    pub exclude_tags: Option<String>,
+    /// search by workspace name, with `*` as a wildcard
+    pub wildcard_name: Option<String>,
+    /// only return workspaces of the given project
+    pub project_id: Option<String>,
+    /// related resources to include, e.g `current_run`
+    pub include: Vec<String>,
+}
+impl ListOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        let mut res = crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+            ("search[name]", self.search.clone()),
+            ("search[tags]", self.tags.clone()),
+            ("search[exclude-tags]", self.exclude_tags.clone()),
+            ("search[wildcard-name]", self.wildcard_name.clone()),
+            ("filter[project][id]", self.project_id.clone()),
+        ]);
+        if !self.include.is_empty() {
+            res.push(("include".to_string(), self.include.join(",")));
+        }
+        res
+    }
+}
+/// This struct contains options for listing remote state consumers of a
+/// workspace.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct RemoteStateConsumersOptions {
+    /// page number to return
+    pub page_number: Option<u32>,
+    /// number of workspaces per page
+    pub page_size: Option<u32>,
+}
+impl RemoteStateConsumersOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        crate::core::query_params([
+            ("page[number]", self.page_number.map(|v| v.to_string())),
+            ("page[size]", self.page_size.map(|v| v.to_string())),
+        ])
+    }
+}
+/// This struct contains options for reading a workspace.
+#[derive(PartialEq, Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// related resources to include, e.g `current_run`
+    pub include: Vec<String>,
+}
+impl ReadOptions {
+    /// returns query parameters of the options
+    pub fn to_query(&self) -> Vec<(String, String)> {
+        if self.include.is_empty() {
+            return Vec::new();
+        }
+        vec![("include".to_string(), self.include.join(","))]
+    }
+}
+/// This struct contains VCS repository settings of a workspace.
+#[derive(PartialEq, Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct VcsRepoOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ingress_submodules: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oauth_token_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub github_app_installation_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags_regex: Option<String>,
+}
+/// This struct contains options for creating a workspace.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct CreateOptions {
+    /// name of the workspace, which must be unique within the organization
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_pool_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_destroy_plan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assessments_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_apply_run_trigger: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_triggers_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_remote_state: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_all_runs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speculative_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structured_run_output_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_patterns: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_prefixes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_repo: Option<VcsRepoOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+    /// ID of the project the workspace is created in. The organization's
+    /// default project is used when unset.
+    #[serde(skip)]
+    pub project_id: Option<String>,
+}
+/// This struct contains options for updating a workspace. Unset fields are
+/// left unchanged.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct UpdateOptions {
+    /// new name of the workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_pool_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_destroy_plan: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assessments_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_apply: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_apply_run_trigger: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_mode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_triggers_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_remote_state: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_all_runs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speculative_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub structured_run_output_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_patterns: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trigger_prefixes: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vcs_repo: Option<VcsRepoOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+    /// ID of the project the workspace is moved to
+    #[serde(skip)]
+    pub project_id: Option<String>,
+}
+/// This struct contains options for locking a workspace.
+#[derive(PartialEq, Debug, Clone, Default, Serialize)]
+pub struct LockOptions {
+    /// reason for locking the workspace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+// ────────────────────────────────────────────────────────────
+/// returns a create or update request document
+fn request_data<'a, T>(
+    attributes: &'a T,
+    project_id: Option<&String>,
+) -> jsonapi::Request<&'a T> {
+    let mut request =
+        jsonapi::Request::builder("workspaces").attributes(attributes);
+    if let Some(project_id) = project_id {
+        request = request.to_one("project", "projects", Some(project_id));
+    }
+    request.build()
+}
+/// returns a request document that references the given workspaces
+fn workspace_identifiers(workspace_ids: &[&str]) -> Relationship<Many> {
+    Relationship::many("workspaces", workspace_ids)
+}
+// ────────────────────────────────────────────────────────────
+/// Workspaces implements the workspaces API.
+#[derive(Debug, Clone)]
+pub struct Workspaces<'a> {
+    client: &'a Client,
+}
+impl Client {
+    /// returns workspaces API
+    pub fn workspaces(&self) -> Workspaces<'_> {
+        Workspaces { client: self }
+    }
+}
+impl<'a> Workspaces<'a> {
+    /// lists workspaces of an organization
+    pub async fn list(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+    ) -> Result<Vec<Workspace>, Error> {
+        validate_id("organization", organization)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> = self
+            .client
+            .get(
+                format!("organizations/{organization}/workspaces"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await?;
+        Ok(response.into_data())
+    }
+    /// returns workspaces of an organization across all pages. The stream ends
+    /// after the last page or after the first error.
+    pub fn list_all(
+        &self,
+        organization: &str,
+        options: &ListOptions,
+        paginator: &PaginatorOptions,
+    ) -> Result<impl Stream<Item = Result<Workspace, Error>>, Error> {
+        validate_id("organization", organization)?;
+        Ok(self.client.paginate(
+            &format!("organizations/{organization}/workspaces"),
+            &options.to_query(),
+            paginator,
+        ))
+    }
+    /// creates a workspace in an organization
+    pub async fn create(
+        &self,
+        organization: &str,
+        options: &CreateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        validate_id("workspace name", options.name.as_str())?;
+        self.client
+            .post(
+                format!("organizations/{organization}/workspaces"),
+                None,
+                request_data(options, options.project_id.as_ref()),
+            )
+            .await
+    }
+    /// reads a workspace by its organization and name
+    pub async fn read(
+        &self,
+        organization: &str,
+        workspace: &str,
+        options: &ReadOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        validate_id("workspace name", workspace)?;
+        let query = options.to_query();
+        self.client
+            .get(
+                format!("organizations/{organization}/workspaces/{workspace}"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await
+    }
+    /// reads a workspace by its ID
+    pub async fn read_by_id(
+        &self,
+        workspace_id: &str,
+        options: &ReadOptions,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        let query = options.to_query();
+        self.client
+            .get(
+                format!("workspaces/{workspace_id}"),
+                None,
+                Some(&crate::core::query(&query)),
+            )
+            .await
+    }
+    /// updates a workspace by its organization and name
+    pub async fn update(
+        &self,
+        organization: &str,
+        workspace: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("organization", organization)?;
+        validate_id("workspace name", workspace)?;
+        self.client
+            .patch(
+                format!("organizations/{organization}/workspaces/{workspace}"),
+                None,
+                request_data(options, options.project_id.as_ref()),
+            )
+            .await
+    }
+    /// updates a workspace by its ID
+    pub async fn update_by_id(
+        &self,
+        workspace_id: &str,
+        options: &UpdateOptions,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .patch(
+                format!("workspaces/{workspace_id}"),
+                None,
+                request_data(options, options.project_id.as_ref()),
+            )
+            .await
+    }
+    /// deletes a workspace by its organization and name
+    pub async fn delete(
+        &self,
+        organization: &str,
+        workspace: &str,
+    ) -> Result<(), Error> {
+        validate_id("organization", organization)?;
+        validate_id("workspace name", workspace)?;
+        self.client
+            .delete(
+                format!("organizations/{organization}/workspaces/{workspace}"),
+                None,
+                (),
+            )
+            .await
+    }
+    /// deletes a workspace by its ID
+    pub async fn delete_by_id(&self, workspace_id: &str) -> Result<(), Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .delete(format!("workspaces/{workspace_id}"), None, ())
+            .await
+    }
+    /// deletes a workspace by its organization and name, only if it is not
+    /// managing any resources
+    pub async fn safe_delete(
+        &self,
+        organization: &str,
+        workspace: &str,
+    ) -> Result<(), Error> {
+        validate_id("organization", organization)?;
+        validate_id("workspace name", workspace)?;
+        self.client
+            .post(
+                format!(
+                    "organizations/{organization}/workspaces/{workspace}/actions/safe-delete"
+                ),
+                None,
+                (),
+            )
+            .await
+    }
+    /// deletes a workspace by its ID, only if it is not managing any
+    /// resources
+    pub async fn safe_delete_by_id(
+        &self,
+        workspace_id: &str,
+    ) -> Result<(), Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .post(
+                format!("workspaces/{workspace_id}/actions/safe-delete"),
+                None,
+                (),
+            )
+            .await
+    }
+    /// locks a workspace
+    pub async fn lock(
+        &self,
+        workspace_id: &str,
+        options: &LockOptions,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .post(
+                format!("workspaces/{workspace_id}/actions/lock"),
+                None,
+                options,
+            )
+            .await
+    }
+    /// unlocks a workspace that was locked by the current API token
+    pub async fn unlock(&self, workspace_id: &str) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .post(
+                format!("workspaces/{workspace_id}/actions/unlock"),
+                None,
+                (),
+            )
+            .await
+    }
+    /// unlocks a workspace regardless of who locked it
+    pub async fn force_unlock(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .post(
+                format!("workspaces/{workspace_id}/actions/force-unlock"),
+                None,
+                (),
+            )
+            .await
+    }
+    /// assigns an SSH key to a workspace
+    pub async fn assign_ssh_key(
+        &self,
+        workspace_id: &str,
+        ssh_key_id: &str,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        validate_id("SSH key ID", ssh_key_id)?;
+        self.client
+            .patch(
+                format!("workspaces/{workspace_id}/relationships/ssh-key"),
+                None,
+                json!({
+                    "data": {
+                        "type": "workspaces",
+                        "attributes": { "id": ssh_key_id }
+                    }
+                }),
+            )
+            .await
+    }
+    /// removes the SSH key that is assigned to a workspace
+    pub async fn unassign_ssh_key(
+        &self,
+        workspace_id: &str,
+    ) -> Result<Response, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        self.client
+            .patch(
+                format!("workspaces/{workspace_id}/relationships/ssh-key"),
+                None,
+                json!({
+                    "data": {
+                        "type": "workspaces",
+                        "attributes": { "id": null }
+                    }
+                }),
+            )
+            .await
+    }
+    /// lists workspaces that can access the state of a workspace
+    pub async fn list_remote_state_consumers(
+        &self,
+        workspace_id: &str,
+        options: &RemoteStateConsumersOptions,
+    ) -> Result<Vec<Workspace>, Error> {
+        validate_id("workspace ID", workspace_id)?;
+        let query = options.to_query();
+        let response: jsonapi::SuccessList<Attributes, Relationships> =
+            self.client
+                .get(
+                    format!(
+                        "workspaces/{workspace_id}/relationships/remote-state-consumers"
+                    ),
+                    None,
+                    Some(&crate::core::query(&query)),
+                )
+                .await?;
+        Ok(response.into_data())
+    }
+    /// allows the given workspaces to access the state of a workspace
+    pub async fn add_remote_state_consumers(
+        &self,
+        workspace_id: &str,
+        consumer_ids: &[&str],
+    ) -> Result<(), Error> {
+        validate_id("workspace ID", workspace_id)?;
+        for id in consumer_ids {
+            validate_id("workspace ID", id)?;
+        }
+        self.client
+            .post(
+                format!(
+                    "workspaces/{workspace_id}/relationships/remote-state-consumers"
+                ),
+                None,
+                workspace_identifiers(consumer_ids),
+            )
+            .await
+    }
+    /// replaces workspaces that can access the state of a workspace
+    pub async fn update_remote_state_consumers(
+        &self,
+        workspace_id: &str,
+        consumer_ids: &[&str],
+    ) -> Result<(), Error> {
+        validate_id("workspace ID", workspace_id)?;
+        for id in consumer_ids {
+            validate_id("workspace ID", id)?;
+        }
+        self.client
+            .patch(
+                format!(
+                    "workspaces/{workspace_id}/relationships/remote-state-consumers"
+                ),
+                None,
+                workspace_identifiers(consumer_ids),
+            )
+            .await
+    }
+    /// revokes access of the given workspaces to the state of a workspace
+    pub async fn remove_remote_state_consumers(
+        &self,
+        workspace_id: &str,
+        consumer_ids: &[&str],
+    ) -> Result<(), Error> {
+        validate_id("workspace ID", workspace_id)?;
+        for id in consumer_ids {
+            validate_id("workspace ID", id)?;
+        }
+        self.client
+            .delete(
+                format!(
+                    "workspaces/{workspace_id}/relationships/remote-state-consumers"
+                ),
+                None,
+                workspace_identifiers(consumer_ids),
+            )
+            .await
+    }
+}
+#[cfg(test)]
+mod tests {
+    // cargo test --all-targets -- "workspaces::tests" --nocapture
+    // cargo watch -cx 'test --all-targets -- "workspaces::tests" --nocapture'
+    use {super::*, serde_json::json};
+    #[test]
+    fn test_response_deserialize() -> Result<(), serde_json::Error> {
+        // NOTE : example is taken from the following
+        // https://developer.hashicorp.com/terraform/cloud-docs/api-docs/workspaces
+        let input = json!({
+            "data": {
+                "id": "ws-SihZTyXKfNXUWuUa",
+                "type": "workspaces",
+                "attributes": {
+                    "name": "workspace-2",
+                    "auto-apply": false,
+                    "execution-mode": "remote",
+                    "locked": false,
+                    "terraform-version": "1.5.7",
+                    "tag-names": ["prod"],
+                    "actions": { "is-destroyable": true },
+                    "permissions": { "can-update": true, "can-lock": true },
+                    "vcs-repo": null
+                },
+                "relationships": {
+                    "organization": {
+                        "data": { "id": "my-organization", "type": "organizations" }
+                    }
+                },
+                "links": { "self": "/api/v2/organizations/my-organization/workspaces/workspace-2" }
+            }
+        });
+        let actual: Response = serde_json::from_value(input)?;
+        let data = actual.get_data();
+        assert_eq!(data.id.as_deref(), Some("ws-SihZTyXKfNXUWuUa"));
+        let attributes = data.attributes.as_ref().unwrap();
+        assert_eq!(attributes.get_name(), "workspace-2");
+        assert_eq!(attributes.execution_mode.as_deref(), Some("remote"));
+        assert_eq!(attributes.tag_names, Some(vec!["prod".to_string()]));
+        assert_eq!(
+            attributes.permissions.as_ref().unwrap().can_lock,
+            Some(true)
+        );
+        let relationships = data.relationships.as_ref().unwrap();
+        assert_eq!(
+            relationships.organization.as_ref().and_then(|v| v.id()),
+            Some("my-organization")
+        );
+        Ok(())
+    }
+    #[test]
+    fn test_list_options_query() {
+        let options = ListOptions {
+            page_size: Some(50),
+            search: Some("prod".to_string()),
+            include: vec![
+                "current_run".to_string(),
+                "organization".to_string(),
+            ],
+            ..Default::default()
+        };
+        let expected = vec![
+            ("page[size]".to_string(), "50".to_string()),
+            ("search[name]".to_string(), "prod".to_string()),
+            (
+                "include".to_string(),
+                "current_run,organization".to_string(),
+            ),
+        ];
+        assert_eq!(options.to_query(), expected);
+        let options = RemoteStateConsumersOptions {
+            page_number: Some(2),
+            ..Default::default()
+        };
+        let expected = vec![("page[number]".to_string(), "2".to_string())];
+        assert_eq!(options.to_query(), expected);
+    }
+    #[test]
+    fn test_create_request() {
+        let options = CreateOptions {
+            name: "workspace-1".to_string(),
+            auto_apply: Some(true),
+            project_id: Some("prj-123".to_string()),
+            ..Default::default()
+        };
+        let expected = json!({
+            "data": {
+                "type": "workspaces",
+                "attributes": { "name": "workspace-1", "auto-apply": true },
+                "relationships": {
+                    "project": {
+                        "data": { "type": "projects", "id": "prj-123" }
+                    }
+                }
+            }
+        });
+        let actual = request_data(&options, options.project_id.as_ref());
+        assert_eq!(serde_json::to_value(actual).unwrap(), expected);
+    }
+}
+// ────────────────────────────────────────────────────────────
+// vim: filetype=rust syntax=rust softtabstop=4 tabstop=4 shiftwidth=4 textwidth=79 fileencoding=utf-8 expandtab
+// code: language=rust insertSpaces=true tabSize=4